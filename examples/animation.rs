@@ -0,0 +1,57 @@
+//! Cycles a sprite through a run of frames. Run with `cargo run --example
+//! animation`.
+//!
+//! There's no dedicated `Animation` type in this tree yet — an animation is
+//! just [`chroma::Chroma::set_tile_sprite`] called on a timer, swapping the
+//! sprite sheet index a tile displays. This example is the pattern any real
+//! `Animation` helper built on top of chroma would wrap.
+
+use chroma::ChromaBuilder;
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoop;
+
+/// Sprite sheet indices for each frame of the animation, played in order and
+/// looped.
+const FRAMES: [u32; 4] = [0, 1, 2, 3];
+const SECONDS_PER_FRAME: f32 = 0.15;
+
+fn main() {
+    env_logger::init();
+
+    let event_loop = EventLoop::new();
+    let mut chroma = ChromaBuilder::new(64, 64)
+        .with_title("chroma - animation")
+        .build(&event_loop)
+        .expect("failed to create Chroma");
+
+    let sprite = chroma.add_tile(cgmath::Vector2::new(24.0, 24.0), FRAMES[0]);
+    let mut current_frame = 0;
+
+    event_loop.run(move |event, _, control_flow| {
+        control_flow.set_poll();
+
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::CloseRequested => control_flow.set_exit(),
+                WindowEvent::Resized(size) => chroma.resize(size),
+                WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                    chroma.resize(*new_inner_size)
+                }
+                _ => {}
+            },
+            Event::MainEventsCleared => chroma.window().request_redraw(),
+            Event::RedrawRequested(_) => {
+                let frame = (chroma.elapsed().as_secs_f32() / SECONDS_PER_FRAME) as usize % FRAMES.len();
+                if frame != current_frame {
+                    current_frame = frame;
+                    chroma
+                        .set_tile_sprite(sprite, FRAMES[current_frame])
+                        .expect("sprite handle is still live");
+                }
+
+                chroma.render().ok();
+            }
+            _ => {}
+        }
+    });
+}
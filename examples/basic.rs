@@ -0,0 +1,43 @@
+//! The smallest possible chroma program: open a window, place one sprite,
+//! and keep it on screen across resizes. Run with `cargo run --example basic`.
+//!
+//! This is a hand-rolled event loop rather than [`chroma::Chroma::run`], to
+//! spell out exactly which events matter and double as a regression test for
+//! the resize path — every winit build of this crate has, at some point,
+//! forgotten to wire `WindowEvent::Resized` into `Chroma::resize`.
+
+use chroma::ChromaBuilder;
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoop;
+
+fn main() {
+    env_logger::init();
+
+    let event_loop = EventLoop::new();
+    let mut chroma = ChromaBuilder::new(128, 128)
+        .with_title("chroma - basic")
+        .build(&event_loop)
+        .expect("failed to create Chroma");
+
+    chroma.add_tile(cgmath::Vector2::new(0.0, 0.0), 0);
+
+    event_loop.run(move |event, _, control_flow| {
+        control_flow.set_poll();
+
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::CloseRequested => control_flow.set_exit(),
+                WindowEvent::Resized(size) => chroma.resize(size),
+                WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                    chroma.resize(*new_inner_size)
+                }
+                _ => {}
+            },
+            Event::MainEventsCleared => chroma.window().request_redraw(),
+            Event::RedrawRequested(_) => {
+                chroma.render().ok();
+            }
+            _ => {}
+        }
+    });
+}
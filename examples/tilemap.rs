@@ -0,0 +1,67 @@
+//! Builds a tilemap from a plain 2D array of sprite indices and scrolls the
+//! camera across it. Run with `cargo run --example tilemap`.
+//!
+//! Chroma doesn't need a dedicated tilemap type for this — a tilemap is just
+//! one [`chroma::Chroma::add_tile`] call per cell, each returning a handle
+//! this example never needs again since nothing in the map moves.
+
+use chroma::ChromaBuilder;
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::EventLoop;
+
+const TILE_SIZE: f32 = 16.0;
+
+#[rustfmt::skip]
+const MAP: [[u32; 10]; 6] = [
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+    [1, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+    [1, 0, 0, 2, 0, 0, 0, 2, 0, 1],
+    [1, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+    [1, 0, 2, 0, 0, 0, 0, 0, 2, 1],
+    [1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+];
+
+fn main() {
+    env_logger::init();
+
+    let event_loop = EventLoop::new();
+    let mut chroma = ChromaBuilder::new(160, 96)
+        .with_title("chroma - tilemap")
+        .build(&event_loop)
+        .expect("failed to create Chroma");
+
+    for (y, row) in MAP.iter().enumerate() {
+        for (x, &index) in row.iter().enumerate() {
+            chroma.add_tile(
+                cgmath::Vector2::new(x as f32 * TILE_SIZE, y as f32 * TILE_SIZE),
+                index,
+            );
+        }
+    }
+
+    event_loop.run(move |event, _, control_flow| {
+        control_flow.set_poll();
+
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::CloseRequested => control_flow.set_exit(),
+                WindowEvent::Resized(size) => chroma.resize(size),
+                WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                    chroma.resize(*new_inner_size)
+                }
+                _ => {}
+            },
+            Event::MainEventsCleared => chroma.window().request_redraw(),
+            Event::RedrawRequested(_) => {
+                // Scroll slowly to the right, wrapping back once the far
+                // edge of the map has passed the left of the viewport.
+                let map_width = MAP[0].len() as f32 * TILE_SIZE;
+                let scroll_x = (chroma.elapsed().as_secs_f32() * 8.0) % map_width;
+                chroma.set_camera_position(cgmath::Vector2::new(scroll_x, 0.0));
+
+                chroma.render().ok();
+            }
+            _ => {}
+        }
+    });
+}
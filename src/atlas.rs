@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+/// A named rectangular region within a sprite sheet texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A sprite sheet with named regions, as produced by TexturePacker's JSON
+/// export ("hash" format).
+#[derive(Debug, Clone)]
+pub struct SpriteAtlas {
+    pub(crate) regions: HashMap<String, AtlasRegion>,
+    pub(crate) image_bytes: Vec<u8>,
+}
+
+#[derive(Error, Debug)]
+pub enum AtlasError {
+    #[error("failed to parse atlas JSON: {0}")]
+    InvalidJson(String),
+
+    #[error("atlas JSON is missing the \"frames\" object")]
+    MissingFrames,
+}
+
+impl SpriteAtlas {
+    /// Parses a TexturePacker JSON ("hash" format) atlas description. Does
+    /// not decode `image_bytes`; pass the raw sheet PNG bytes alongside the
+    /// JSON — [`crate::Chroma::load_atlas`] uploads them together.
+    pub fn from_json(json: &[u8], image_bytes: Vec<u8>) -> Result<Self, AtlasError> {
+        let value: serde_json::Value =
+            serde_json::from_slice(json).map_err(|e| AtlasError::InvalidJson(e.to_string()))?;
+
+        let frames = value
+            .get("frames")
+            .and_then(|f| f.as_object())
+            .ok_or(AtlasError::MissingFrames)?;
+
+        let mut regions = HashMap::with_capacity(frames.len());
+        for (name, frame) in frames {
+            let rect = &frame["frame"];
+            regions.insert(
+                name.clone(),
+                AtlasRegion {
+                    x: rect["x"].as_u64().unwrap_or(0) as u32,
+                    y: rect["y"].as_u64().unwrap_or(0) as u32,
+                    width: rect["w"].as_u64().unwrap_or(0) as u32,
+                    height: rect["h"].as_u64().unwrap_or(0) as u32,
+                },
+            );
+        }
+
+        Ok(Self { regions, image_bytes })
+    }
+
+    pub fn region(&self, name: &str) -> Option<AtlasRegion> {
+        self.regions.get(name).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_json_parses_regions_out_of_the_frames_object() {
+        let json = br#"{
+            "frames": {
+                "player.png": { "frame": { "x": 1, "y": 2, "w": 16, "h": 32 } },
+                "enemy.png": { "frame": { "x": 20, "y": 0, "w": 8, "h": 8 } }
+            }
+        }"#;
+        let atlas = SpriteAtlas::from_json(json, vec![0xFF]).unwrap();
+        assert_eq!(
+            atlas.region("player.png"),
+            Some(AtlasRegion { x: 1, y: 2, width: 16, height: 32 })
+        );
+        assert_eq!(
+            atlas.region("enemy.png"),
+            Some(AtlasRegion { x: 20, y: 0, width: 8, height: 8 })
+        );
+        assert_eq!(atlas.region("missing.png"), None);
+    }
+
+    #[test]
+    fn from_json_rejects_malformed_json() {
+        let json = b"not json";
+        assert!(matches!(
+            SpriteAtlas::from_json(json, Vec::new()),
+            Err(AtlasError::InvalidJson(_))
+        ));
+    }
+
+    #[test]
+    fn from_json_rejects_a_document_missing_frames() {
+        let json = br#"{ "meta": {} }"#;
+        assert!(matches!(
+            SpriteAtlas::from_json(json, Vec::new()),
+            Err(AtlasError::MissingFrames)
+        ));
+    }
+}
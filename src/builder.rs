@@ -0,0 +1,92 @@
+use crate::{Chroma, ChromaError};
+
+/// Configures and constructs a [`Chroma`] instance.
+#[derive(Debug, Clone)]
+pub struct ChromaBuilder {
+    virtual_width: u32,
+    virtual_height: u32,
+    window_title: String,
+    features: wgpu::Features,
+}
+
+impl ChromaBuilder {
+    pub fn new(virtual_width: u32, virtual_height: u32) -> Self {
+        Self {
+            virtual_width,
+            virtual_height,
+            window_title: "chroma".to_string(),
+            features: wgpu::Features::empty(),
+        }
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.window_title = title.into();
+        self
+    }
+
+    /// Requests additional GPU features (push constants, texture binding
+    /// arrays, timestamp queries, ...) beyond chroma's own defaults, for a
+    /// downstream render pass to use. Errors at [`ChromaBuilder::build`]
+    /// time if the adapter doesn't support them.
+    pub fn with_features(mut self, features: wgpu::Features) -> Self {
+        self.features = features;
+        self
+    }
+
+    pub fn build(self, event_loop: &winit::event_loop::EventLoop<()>) -> Result<Chroma, ChromaError> {
+        let window = winit::window::WindowBuilder::new()
+            .with_title(self.window_title)
+            .build(event_loop)
+            .expect("failed to create window");
+
+        Chroma::new_with_features(window, (self.virtual_width, self.virtual_height), self.features)
+    }
+}
+
+/// Creates a window sized for a `pixel_width`x`pixel_height` game, picking
+/// the largest integer scale that fits the primary monitor (or
+/// `preferred_scale`, if given) and accounting for the monitor's scale
+/// factor so the window doesn't end up half its intended size on a HiDPI
+/// display. Also sets a matching minimum inner size and resize increments,
+/// so the window can't be dragged below one game pixel or land on a
+/// fractional scale by accident.
+///
+/// Errors with [`ChromaError::NoMonitorAvailable`] instead of panicking if
+/// the platform reports no primary monitor (e.g. some headless CI setups),
+/// and with [`ChromaError::InvalidSurfaceSize`] instead of panicking if
+/// `pixel_width`/`pixel_height` are zero (they're divided into the
+/// monitor size to pick a scale).
+pub fn create_window(
+    event_loop: &winit::event_loop::EventLoop<()>,
+    title: impl Into<String>,
+    pixel_width: u32,
+    pixel_height: u32,
+    preferred_scale: Option<u32>,
+) -> Result<winit::window::Window, ChromaError> {
+    if pixel_width == 0 || pixel_height == 0 {
+        return Err(ChromaError::InvalidSurfaceSize { width: pixel_width, height: pixel_height });
+    }
+
+    let monitor = event_loop.primary_monitor().ok_or(ChromaError::NoMonitorAvailable)?;
+    let logical_monitor_size: winit::dpi::LogicalSize<u32> =
+        monitor.size().to_logical(monitor.scale_factor());
+
+    let scale = preferred_scale.unwrap_or_else(|| {
+        (logical_monitor_size.width / pixel_width)
+            .min(logical_monitor_size.height / pixel_height)
+            .max(1)
+    });
+
+    let inner_size = winit::dpi::LogicalSize::new(pixel_width * scale, pixel_height * scale);
+    let min_inner_size = winit::dpi::LogicalSize::new(pixel_width, pixel_height);
+
+    let window = winit::window::WindowBuilder::new()
+        .with_title(title)
+        .with_inner_size(inner_size)
+        .with_min_inner_size(min_inner_size)
+        .with_resize_increments(min_inner_size)
+        .build(event_loop)
+        .expect("failed to create window");
+
+    Ok(window)
+}
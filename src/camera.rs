@@ -0,0 +1,77 @@
+/// How a fractional camera offset is handled when uploaded to the GPU.
+///
+/// A camera that moves by fractional virtual pixels makes sprites shimmer
+/// as they cross pixel boundaries unless the offset is snapped to whole
+/// pixels; snapping in turn makes scrolling look "steppy" at low virtual
+/// resolutions.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CameraSnap {
+    /// Round the camera offset to whole pixels before upload. Crisp, but
+    /// scrolling advances in whole-pixel steps.
+    #[default]
+    Snap,
+    /// Upload the camera offset as-is. Smooth, but sprites can shimmer.
+    Smooth,
+    /// Round the camera offset for the sprite pass, and apply the
+    /// fractional remainder as an extra translation in the upscale pass so
+    /// scrolling looks smooth at high window scale factors.
+    SnapWithOffsetPass,
+}
+
+/// The camera offset actually uploaded to the sprite pass, plus any
+/// remainder to be applied by the upscale pass under
+/// [`CameraSnap::SnapWithOffsetPass`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraUniform {
+    pub sprite_pass_offset: cgmath::Vector2<f32>,
+    pub upscale_pass_remainder: cgmath::Vector2<f32>,
+}
+
+impl CameraSnap {
+    pub fn uniform(self, camera: cgmath::Vector2<f32>) -> CameraUniform {
+        match self {
+            CameraSnap::Smooth => CameraUniform {
+                sprite_pass_offset: camera,
+                upscale_pass_remainder: cgmath::Vector2::new(0.0, 0.0),
+            },
+            CameraSnap::Snap => CameraUniform {
+                sprite_pass_offset: cgmath::Vector2::new(camera.x.round(), camera.y.round()),
+                upscale_pass_remainder: cgmath::Vector2::new(0.0, 0.0),
+            },
+            CameraSnap::SnapWithOffsetPass => {
+                let snapped = cgmath::Vector2::new(camera.x.round(), camera.y.round());
+                CameraUniform {
+                    sprite_pass_offset: snapped,
+                    upscale_pass_remainder: camera - snapped,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smooth_passes_offset_through_unchanged() {
+        let uniform = CameraSnap::Smooth.uniform(cgmath::Vector2::new(10.4, 3.7));
+        assert_eq!(uniform.sprite_pass_offset, cgmath::Vector2::new(10.4, 3.7));
+        assert_eq!(uniform.upscale_pass_remainder, cgmath::Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn snap_rounds_to_whole_pixels() {
+        let uniform = CameraSnap::Snap.uniform(cgmath::Vector2::new(10.4, 3.7));
+        assert_eq!(uniform.sprite_pass_offset, cgmath::Vector2::new(10.0, 4.0));
+        assert_eq!(uniform.upscale_pass_remainder, cgmath::Vector2::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn snap_with_offset_pass_splits_the_remainder() {
+        let uniform = CameraSnap::SnapWithOffsetPass.uniform(cgmath::Vector2::new(10.4, 3.7));
+        assert_eq!(uniform.sprite_pass_offset, cgmath::Vector2::new(10.0, 4.0));
+        assert!((uniform.upscale_pass_remainder.x - 0.4).abs() < 1e-5);
+        assert!((uniform.upscale_pass_remainder.y - (-0.3)).abs() < 1e-5);
+    }
+}
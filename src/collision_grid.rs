@@ -0,0 +1,110 @@
+/// Bitfield of collision properties a single [`CollisionGrid`] cell can
+/// carry. Combine flags with `|`, e.g. `CollisionFlags::SOLID | CollisionFlags::HAZARD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CollisionFlags(u8);
+
+impl CollisionFlags {
+    pub const NONE: Self = Self(0);
+    pub const SOLID: Self = Self(1 << 0);
+    pub const PLATFORM: Self = Self(1 << 1);
+    pub const WATER: Self = Self(1 << 2);
+    pub const HAZARD: Self = Self(1 << 3);
+
+    /// Whether every flag set in `other` is also set in `self`.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for CollisionFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for CollisionFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A grid of per-tile [`CollisionFlags`], stored separately from the
+/// visual tilemap so collision data can be authored or generated (e.g. from
+/// an LDtk IntGrid layer) independently of what's drawn. See
+/// [`crate::Chroma::set_collision_grid`] and [`crate::Chroma::tile_at_position`].
+#[derive(Debug)]
+pub struct CollisionGrid {
+    pub width: u32,
+    pub height: u32,
+    pub cells: Vec<CollisionFlags>,
+}
+
+impl CollisionGrid {
+    /// Creates a `width` by `height` grid with every cell set to
+    /// [`CollisionFlags::NONE`].
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![CollisionFlags::NONE; (width * height) as usize],
+        }
+    }
+
+    /// The flags at `(x, y)`, or [`CollisionFlags::NONE`] if out of bounds.
+    pub fn flags_at(&self, x: u32, y: u32) -> CollisionFlags {
+        if x >= self.width || y >= self.height {
+            return CollisionFlags::NONE;
+        }
+        self.cells[(y * self.width + x) as usize]
+    }
+
+    /// Sets the flags at `(x, y)`. A no-op if out of bounds.
+    pub fn set(&mut self, x: u32, y: u32, flags: CollisionFlags) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        self.cells[(y * self.width + x) as usize] = flags;
+    }
+
+    /// Whether `(x, y)` carries [`CollisionFlags::SOLID`]. `false` if out of
+    /// bounds.
+    pub fn is_solid(&self, x: u32, y: u32) -> bool {
+        self.flags_at(x, y).contains(CollisionFlags::SOLID)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_grid_has_no_collision() {
+        let grid = CollisionGrid::new(4, 4);
+        assert!(!grid.is_solid(1, 1));
+    }
+
+    #[test]
+    fn set_and_query_solid_flag() {
+        let mut grid = CollisionGrid::new(4, 4);
+        grid.set(2, 3, CollisionFlags::SOLID);
+        assert!(grid.is_solid(2, 3));
+        assert!(!grid.is_solid(2, 2));
+    }
+
+    #[test]
+    fn combined_flags_are_queryable_independently() {
+        let mut grid = CollisionGrid::new(2, 2);
+        grid.set(0, 0, CollisionFlags::SOLID | CollisionFlags::HAZARD);
+        assert!(grid.is_solid(0, 0));
+        assert!(grid.flags_at(0, 0).contains(CollisionFlags::HAZARD));
+        assert!(!grid.flags_at(0, 0).contains(CollisionFlags::WATER));
+    }
+
+    #[test]
+    fn out_of_bounds_queries_report_no_collision() {
+        let grid = CollisionGrid::new(2, 2);
+        assert!(!grid.is_solid(5, 5));
+    }
+}
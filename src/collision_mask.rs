@@ -0,0 +1,140 @@
+/// A per-pixel opacity bitmask for one sprite-sheet tile, one bit per pixel
+/// (`1` = opaque), for pixel-perfect overlap tests finer than a bounding
+/// box. Rows are packed into `u64` words for compact storage and cheap bit
+/// tests in [`CollisionMask::overlaps`].
+#[derive(Debug)]
+pub struct CollisionMask {
+    width: u32,
+    height: u32,
+    rows: Vec<Vec<u64>>,
+}
+
+impl CollisionMask {
+    /// Extracts one [`CollisionMask`] per `tile_w` by `tile_h` cell of an
+    /// RGBA8 sprite sheet that's `columns` tiles wide, in row-major order.
+    /// A pixel is opaque if its alpha channel is nonzero.
+    pub fn from_sprite_sheet(rgba: &[u8], tile_w: u32, tile_h: u32, columns: u32) -> Vec<Self> {
+        if tile_w == 0 || tile_h == 0 || columns == 0 {
+            return Vec::new();
+        }
+
+        let sheet_width = columns * tile_w;
+        let tile_count = (rgba.len() as u32 / 4) / (tile_w * tile_h);
+        let words_per_row = (tile_w as usize).div_ceil(64);
+
+        (0..tile_count)
+            .map(|tile_index| {
+                let tile_x = (tile_index % columns) * tile_w;
+                let tile_y = (tile_index / columns) * tile_h;
+                let mut rows = vec![vec![0u64; words_per_row]; tile_h as usize];
+
+                for (y, row) in rows.iter_mut().enumerate() {
+                    for x in 0..tile_w {
+                        let px = tile_x + x;
+                        let py = tile_y + y as u32;
+                        let alpha_offset = ((py * sheet_width + px) * 4 + 3) as usize;
+                        if rgba.get(alpha_offset).is_some_and(|&alpha| alpha != 0) {
+                            row[(x / 64) as usize] |= 1u64 << (x % 64);
+                        }
+                    }
+                }
+
+                Self { width: tile_w, height: tile_h, rows }
+            })
+            .collect()
+    }
+
+    /// Whether the pixel at `(x, y)` is opaque. `false` if out of bounds.
+    fn is_opaque(&self, x: u32, y: u32) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let word = self.rows[y as usize][(x / 64) as usize];
+        (word >> (x % 64)) & 1 != 0
+    }
+
+    /// Whether any opaque pixel of `self` at `self_pos` overlaps an opaque
+    /// pixel of `other` at `other_pos`, both positions in the same
+    /// coordinate space (e.g. two sprites' world positions).
+    pub fn overlaps(&self, self_pos: (i32, i32), other: &CollisionMask, other_pos: (i32, i32)) -> bool {
+        let overlap_x0 = self_pos.0.max(other_pos.0);
+        let overlap_y0 = self_pos.1.max(other_pos.1);
+        let overlap_x1 = (self_pos.0 + self.width as i32).min(other_pos.0 + other.width as i32);
+        let overlap_y1 = (self_pos.1 + self.height as i32).min(other_pos.1 + other.height as i32);
+
+        for y in overlap_y0..overlap_y1 {
+            for x in overlap_x0..overlap_x1 {
+                let self_opaque = self.is_opaque((x - self_pos.0) as u32, (y - self_pos.1) as u32);
+                let other_opaque = other.is_opaque((x - other_pos.0) as u32, (y - other_pos.1) as u32);
+                if self_opaque && other_opaque {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_tile(w: u32, h: u32) -> Vec<u8> {
+        vec![255; (w * h * 4) as usize]
+    }
+
+    #[test]
+    fn extracts_one_mask_per_tile() {
+        // A 4x4-pixel sheet split into four 2x2 tiles (2 columns, 2 rows).
+        let sheet = solid_tile(4, 4);
+        let masks = CollisionMask::from_sprite_sheet(&sheet, 2, 2, 2);
+        assert_eq!(masks.len(), 4);
+    }
+
+    #[test]
+    fn transparent_pixels_are_not_opaque() {
+        let mut sheet = vec![0u8; 2 * 2 * 4];
+        // Top-left pixel opaque, everything else transparent.
+        sheet[3] = 255;
+        let masks = CollisionMask::from_sprite_sheet(&sheet, 2, 2, 1);
+        assert!(masks[0].is_opaque(0, 0));
+        assert!(!masks[0].is_opaque(1, 0));
+        assert!(!masks[0].is_opaque(0, 1));
+    }
+
+    #[test]
+    fn overlapping_solid_tiles_collide() {
+        let sheet = solid_tile(4, 4);
+        let masks = CollisionMask::from_sprite_sheet(&sheet, 4, 4, 1);
+        let a = &masks[0];
+        let b = &masks[0];
+        assert!(a.overlaps((0, 0), b, (2, 2)));
+    }
+
+    #[test]
+    fn non_overlapping_tiles_do_not_collide() {
+        let sheet = solid_tile(4, 4);
+        let masks = CollisionMask::from_sprite_sheet(&sheet, 4, 4, 1);
+        let a = &masks[0];
+        let b = &masks[0];
+        assert!(!a.overlaps((0, 0), b, (10, 10)));
+    }
+
+    #[test]
+    fn bounding_boxes_touch_but_opaque_pixels_do_not() {
+        // A only has an opaque top-left pixel; B only has an opaque
+        // bottom-right pixel. Their bounding boxes overlap but the shapes
+        // don't.
+        let mut sheet = vec![0u8; 2 * 2 * 4];
+        sheet[3] = 255; // (0, 0) opaque
+        let masks = CollisionMask::from_sprite_sheet(&sheet, 2, 2, 1);
+        let a = &masks[0];
+
+        let mut sheet_b = vec![0u8; 2 * 2 * 4];
+        sheet_b[(3 * 4) + 3] = 255; // (1, 1) opaque
+        let masks_b = CollisionMask::from_sprite_sheet(&sheet_b, 2, 2, 1);
+        let b = &masks_b[0];
+
+        assert!(!a.overlaps((0, 0), b, (1, 1)));
+    }
+}
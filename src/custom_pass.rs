@@ -0,0 +1,24 @@
+/// An extension point for inserting a whole extra render pass into
+/// [`crate::Chroma::render`], registered with
+/// [`crate::Chroma::add_custom_pass`]. Runs after the sprite pass and
+/// before the frame is submitted — for post-processing, UI overlays, or
+/// debugging layers that need their own pipeline rather than a hook inside
+/// the sprite pass (see [`crate::Chroma::with_sprite_pass_hook`] for that).
+pub trait CustomRenderPass {
+    /// Encodes this pass's commands into `encoder`.
+    ///
+    /// `intermediate_view` is the game image before the upscale pass and
+    /// `surface_view` is the final window surface. This engine currently
+    /// renders sprites straight to the surface (there's no separate
+    /// offscreen game-image texture yet), so both parameters are the same
+    /// view today; they're kept distinct so passes written against this
+    /// trait keep working once an intermediate target exists.
+    fn encode(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        intermediate_view: &wgpu::TextureView,
+        surface_view: &wgpu::TextureView,
+    );
+}
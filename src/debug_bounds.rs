@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+/// Per-layer bounding-box outline colors for
+/// [`crate::Chroma::enable_debug_bounds`] and
+/// [`crate::Chroma::enable_debug_bounds_on_layer`]. Drawn as a one-pixel
+/// outline rect around each instance's virtual-pixel bounding box, after all
+/// sprites are composited but before the upscale pass, so outlines land on
+/// exact virtual pixels.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DebugBounds {
+    default_color: Option<[f32; 4]>,
+    layer_colors: HashMap<u8, [f32; 4]>,
+}
+
+impl DebugBounds {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn set_default(&mut self, color: Option<[f32; 4]>) {
+        self.default_color = color;
+    }
+
+    pub(crate) fn set_layer(&mut self, layer: u8, color: [f32; 4]) {
+        self.layer_colors.insert(layer, color);
+    }
+
+    /// The outline color to draw for `layer`, preferring a per-layer
+    /// override over the default set by `enable_debug_bounds`. `None` if
+    /// neither is configured for this layer.
+    pub(crate) fn color_for(&self, layer: u8) -> Option<[f32; 4]> {
+        self.layer_colors.get(&layer).copied().or(self.default_color)
+    }
+}
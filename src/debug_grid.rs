@@ -0,0 +1,39 @@
+/// An overlay of `grid_size`-pixel squares (borders only) on the virtual
+/// framebuffer, for aligning sprites to the pixel grid during development.
+/// Drawn on top of every sprite instance but below any UI layer.
+///
+/// Toggling doesn't forget the configured size and color, so
+/// [`crate::Chroma::toggle_debug_grid`] can flip the overlay on and off
+/// without a re-`enable_debug_grid` call.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DebugGrid {
+    grid_size: u32,
+    color: [f32; 4],
+    enabled: bool,
+}
+
+impl DebugGrid {
+    pub(crate) fn new(grid_size: u32, color: [f32; 4]) -> Self {
+        Self {
+            grid_size,
+            color,
+            enabled: true,
+        }
+    }
+
+    pub(crate) fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn grid_size(&self) -> u32 {
+        self.grid_size
+    }
+
+    pub(crate) fn color(&self) -> [f32; 4] {
+        self.color
+    }
+}
@@ -0,0 +1,30 @@
+//! A thin bridge to [`hecs`](https://docs.rs/hecs) for games that keep
+//! their world state in an ECS rather than driving [`crate::Chroma`]
+//! directly. Only available with the `hecs` feature enabled.
+
+use crate::{Chroma, TileHandle};
+
+/// Types that describe a world-space position convertible to chroma's
+/// virtual pixel space, so [`sync_chroma_transforms`] can work with
+/// whatever position component a game already uses.
+pub trait IntoVirtualPixel {
+    fn into_virtual_pixel(&self) -> cgmath::Vector2<f32>;
+}
+
+/// ECS component marking an entity as backed by a chroma sprite. Wraps the
+/// [`TileHandle`] returned when the sprite was added via
+/// [`crate::Chroma::add_tile`] or similar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChromaRenderer(pub TileHandle);
+
+/// Moves every entity's chroma sprite to match its `Position` component,
+/// for entities carrying both a `Position` and a [`ChromaRenderer`].
+/// Call once per frame, before [`crate::Chroma::render`].
+pub fn sync_chroma_transforms<Position>(world: &hecs::World, chroma: &mut Chroma)
+where
+    Position: hecs::Component + IntoVirtualPixel,
+{
+    for (_entity, (position, renderer)) in world.query::<(&Position, &ChromaRenderer)>().iter() {
+        chroma.move_tile(renderer.0, position.into_virtual_pixel());
+    }
+}
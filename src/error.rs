@@ -0,0 +1,79 @@
+use thiserror::Error;
+
+/// Errors that can occur while driving a [`crate::Chroma`] instance.
+#[derive(Error, Debug)]
+pub enum ChromaError {
+    #[error("failed to create surface: {0}")]
+    SurfaceCreation(#[from] wgpu::CreateSurfaceError),
+
+    #[error("no suitable GPU adapter found")]
+    AdapterNotFound,
+
+    #[error("failed to request GPU device: {0}")]
+    DeviceRequest(#[from] wgpu::RequestDeviceError),
+
+    #[error("surface error: {0}")]
+    Surface(#[from] wgpu::SurfaceError),
+
+    #[error("failed to decode image: {0}")]
+    ImageDecode(#[from] image::ImageError),
+
+    #[error("tile handle does not refer to a live tile")]
+    InvalidTileHandle,
+
+    #[error("no atlas region with that name")]
+    UnknownAtlasRegion,
+
+    #[error("adapter does not support requested features: {0:?}")]
+    FeatureNotSupported(wgpu::Features),
+
+    #[error("surface does not support texture format {requested:?}; available formats: {available:?}")]
+    UnsupportedTextureFormat {
+        requested: wgpu::TextureFormat,
+        available: Vec<wgpu::TextureFormat>,
+    },
+
+    #[error("failed to parse LDtk project: {0}")]
+    LdtkParse(#[from] serde_json::Error),
+
+    #[error("no LDtk level named {0:?}")]
+    UnknownLdtkLevel(String),
+
+    #[error("texture data is {actual} bytes, expected {expected} for an RGBA8 image of that size")]
+    InvalidTextureData { expected: usize, actual: usize },
+
+    #[error("texture handle does not refer to a live texture")]
+    InvalidTextureHandle,
+
+    #[error("failed to read file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("surface size must be non-zero in both dimensions, got {width}x{height}")]
+    InvalidSurfaceSize { width: u32, height: u32 },
+
+    #[error("snapshot is {actual} bytes, expected {expected} for the current frame buffer size")]
+    InvalidSnapshotLength { expected: usize, actual: usize },
+
+    #[error("failed to map buffer for readback: {0}")]
+    BufferMap(String),
+
+    #[error("no monitor available to size the window against")]
+    NoMonitorAvailable,
+
+    #[error("PixelsBuilder::build called without a surface_texture; call PixelsBuilder::surface_texture() first")]
+    MissingSurfaceTexture,
+
+    #[cfg(all(feature = "async-loading", target_arch = "wasm32"))]
+    #[error("failed to fetch resource: {0}")]
+    Fetch(String),
+}
+
+/// Errors from serializing or deserializing scene state.
+///
+/// Only available with the `serde` feature enabled.
+#[cfg(feature = "serde")]
+#[derive(Error, Debug)]
+pub enum SerializeError {
+    #[error("failed to encode instances: {0}")]
+    Encode(#[from] bincode::Error),
+}
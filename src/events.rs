@@ -0,0 +1,96 @@
+use std::cell::RefCell;
+
+/// A FIFO queue of `E`-typed events, decoupling the system that pushes an
+/// event from the one that drains it.
+///
+/// [`EventQueue::push`] and [`EventQueue::drain`] both take `&self`
+/// (backed by a [`RefCell`]) so any number of systems holding a shared
+/// reference — such as the one returned by [`crate::Chroma::events`] — can
+/// push events without needing `&mut Chroma`.
+#[derive(Debug)]
+pub struct EventQueue<E>
+where
+    E: 'static + Send,
+{
+    events: RefCell<Vec<E>>,
+}
+
+impl<E> EventQueue<E>
+where
+    E: 'static + Send,
+{
+    pub fn new() -> Self {
+        Self {
+            events: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Queues `event` to be returned by the next [`EventQueue::drain`].
+    pub fn push(&self, event: E) {
+        self.events.borrow_mut().push(event);
+    }
+
+    /// Removes and returns every event queued since the last drain, oldest
+    /// first.
+    pub fn drain(&self) -> std::vec::IntoIter<E> {
+        self.events.borrow_mut().drain(..).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<E> Default for EventQueue<E>
+where
+    E: 'static + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Events [`crate::Chroma`] itself emits. Games push their own event types
+/// into their own [`EventQueue`]s; this enum is only for the engine's.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChromaEvent {
+    /// An animation finished playing, naming the sprite and the animation.
+    /// Nothing in this build pushes this yet — there's no animation system
+    /// to source it from — but it's reserved for when one lands.
+    AnimationComplete(crate::TileHandle, String),
+    /// A tile was added via [`crate::Chroma::add_tile`] or
+    /// [`crate::Chroma::add_tile_anchored`].
+    TileAdded(crate::TileHandle),
+    /// The window was resized to the given physical size.
+    WindowResized(u32, u32),
+    /// Pushed by [`crate::StreamingTileMap::update`] when the instance
+    /// memory it's keeping loaded exceeds the limit set via
+    /// [`crate::StreamingTileMap::set_memory_limit_bytes`].
+    MemoryPressure { used_bytes: usize, limit_bytes: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_returns_events_in_push_order() {
+        let queue = EventQueue::new();
+        queue.push(1);
+        queue.push(2);
+        queue.push(3);
+        assert_eq!(queue.drain().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn drain_empties_the_queue() {
+        let queue = EventQueue::new();
+        queue.push("a");
+        queue.drain().for_each(drop);
+        assert_eq!(queue.drain().collect::<Vec<_>>(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn push_does_not_require_a_mutable_reference() {
+        let queue = EventQueue::new();
+        let shared: &EventQueue<u32> = &queue;
+        shared.push(42);
+        assert_eq!(shared.drain().collect::<Vec<_>>(), vec![42]);
+    }
+}
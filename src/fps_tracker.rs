@@ -0,0 +1,69 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How many frame deltas the rolling average is computed over.
+const HISTORY_LEN: usize = 60;
+
+/// Rolling frame-time statistics over the last [`HISTORY_LEN`] frames,
+/// updated by [`crate::Chroma::update_fps`] (called automatically from
+/// [`crate::Chroma::render`]).
+#[derive(Debug)]
+pub struct FpsTracker {
+    deltas: VecDeque<Duration>,
+    last_frame: Option<Instant>,
+}
+
+impl FpsTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            deltas: VecDeque::with_capacity(HISTORY_LEN),
+            last_frame: None,
+        }
+    }
+
+    pub(crate) fn update(&mut self, now: Instant) {
+        if let Some(last) = self.last_frame {
+            if self.deltas.len() == HISTORY_LEN {
+                self.deltas.pop_front();
+            }
+            self.deltas.push_back(now.duration_since(last));
+        }
+        self.last_frame = Some(now);
+    }
+
+    /// The rolling average frames-per-second over the tracked history, or
+    /// `0.0` before a second frame has been recorded.
+    pub fn fps(&self) -> f64 {
+        if self.deltas.is_empty() {
+            return 0.0;
+        }
+        let total: Duration = self.deltas.iter().sum();
+        self.deltas.len() as f64 / total.as_secs_f64()
+    }
+
+    /// The lowest instantaneous FPS (i.e. from the longest single frame
+    /// delta) in the tracked history.
+    pub fn min_fps(&self) -> f64 {
+        if self.deltas.is_empty() {
+            return 0.0;
+        }
+        self.deltas
+            .iter()
+            .map(|d| 1.0 / d.as_secs_f64())
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// The highest instantaneous FPS (i.e. from the shortest single frame
+    /// delta) in the tracked history.
+    pub fn max_fps(&self) -> f64 {
+        if self.deltas.is_empty() {
+            return 0.0;
+        }
+        self.deltas.iter().map(|d| 1.0 / d.as_secs_f64()).fold(0.0, f64::max)
+    }
+
+    /// The most recent frame's time in milliseconds.
+    pub fn frame_time_ms(&self) -> f64 {
+        self.deltas.back().map_or(0.0, |d| d.as_secs_f64() * 1000.0)
+    }
+}
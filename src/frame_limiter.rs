@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+/// The target time budget per presented frame requested via
+/// [`crate::Chroma::set_frame_limit`]. Kept separate from the sleeping
+/// itself (see [`crate::Chroma::render`]) so the target-time math — the part
+/// that's actually worth a deterministic unit test — doesn't depend on wall
+/// clock time.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct FrameLimiter {
+    target_fps: Option<u32>,
+}
+
+impl FrameLimiter {
+    pub(crate) fn set_limit(&mut self, target_fps: Option<u32>) {
+        self.target_fps = target_fps;
+    }
+
+    /// How long a frame should take to hit `target_fps`, or `None` if
+    /// unlimited.
+    pub(crate) fn target_frame_duration(&self) -> Option<Duration> {
+        self.target_fps.map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+    }
+
+    /// How long to still sleep this frame, given `elapsed` since the last
+    /// present — `Duration::ZERO` if unlimited or already at/over budget.
+    pub(crate) fn remaining(&self, elapsed: Duration) -> Duration {
+        match self.target_frame_duration() {
+            Some(target) => target.saturating_sub(elapsed),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_by_default() {
+        assert_eq!(FrameLimiter::default().target_frame_duration(), None);
+        assert_eq!(FrameLimiter::default().remaining(Duration::from_millis(100)), Duration::ZERO);
+    }
+
+    #[test]
+    fn sixty_fps_targets_roughly_16_67ms() {
+        let mut limiter = FrameLimiter::default();
+        limiter.set_limit(Some(60));
+        let target = limiter.target_frame_duration().unwrap();
+        assert!((target.as_secs_f64() - 1.0 / 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn remaining_is_zero_once_the_frame_already_took_the_full_budget() {
+        let mut limiter = FrameLimiter::default();
+        limiter.set_limit(Some(60));
+        assert_eq!(limiter.remaining(Duration::from_secs_f64(1.0 / 60.0)), Duration::ZERO);
+        assert_eq!(limiter.remaining(Duration::from_secs(1)), Duration::ZERO);
+    }
+
+    #[test]
+    fn remaining_covers_the_rest_of_the_budget() {
+        let mut limiter = FrameLimiter::default();
+        limiter.set_limit(Some(60));
+        let elapsed = Duration::from_secs_f64(1.0 / 60.0 - 0.005);
+        assert!((limiter.remaining(elapsed).as_secs_f64() - 0.005).abs() < 1e-6);
+    }
+
+    #[test]
+    fn clearing_the_limit_removes_the_target() {
+        let mut limiter = FrameLimiter::default();
+        limiter.set_limit(Some(30));
+        limiter.set_limit(None);
+        assert_eq!(limiter.target_frame_duration(), None);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// How many frame times [`FrameStats`]'s percentile window covers.
+const WINDOW_LEN: usize = 120;
+
+/// Per-frame performance counters, updated automatically by
+/// [`crate::Chroma::render`]. Unlike [`crate::FpsTracker`] (a short rolling
+/// average meant for a live FPS readout), this keeps a longer window for
+/// percentile queries and also tracks scene-side counts that aren't timing
+/// data at all.
+#[derive(Debug)]
+pub struct FrameStats {
+    frame_times: VecDeque<Duration>,
+    instance_count: usize,
+    instance_buffer_uploads: u64,
+}
+
+impl FrameStats {
+    pub(crate) fn new() -> Self {
+        Self {
+            frame_times: VecDeque::with_capacity(WINDOW_LEN),
+            instance_count: 0,
+            instance_buffer_uploads: 0,
+        }
+    }
+
+    pub(crate) fn record_frame(
+        &mut self,
+        frame_time: Duration,
+        instance_count: usize,
+        instance_buffer_uploads: u64,
+    ) {
+        if self.frame_times.len() == WINDOW_LEN {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+        self.instance_count = instance_count;
+        self.instance_buffer_uploads = instance_buffer_uploads;
+    }
+
+    /// FPS computed from the most recent frame's time alone. `0.0` before
+    /// the first frame.
+    pub fn instantaneous_fps(&self) -> f64 {
+        self.frame_times.back().map_or(0.0, |d| 1.0 / d.as_secs_f64())
+    }
+
+    /// FPS averaged over the tracked window. `0.0` before the first frame.
+    pub fn smoothed_fps(&self) -> f64 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        let total: Duration = self.frame_times.iter().sum();
+        self.frame_times.len() as f64 / total.as_secs_f64()
+    }
+
+    /// The `p`th percentile (`0.0..=100.0`) frame time in milliseconds over
+    /// the tracked window, e.g. `frame_time_percentile_ms(99.0)` for p99.
+    /// `0.0` before the first frame.
+    pub fn frame_time_percentile_ms(&self, p: f64) -> f64 {
+        if self.frame_times.is_empty() {
+            return 0.0;
+        }
+        let mut sorted: Vec<f64> = self
+            .frame_times
+            .iter()
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let rank = ((p / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+        sorted[rank.min(sorted.len() - 1)]
+    }
+
+    /// The instance count as of the most recently recorded frame.
+    pub fn instance_count(&self) -> usize {
+        self.instance_count
+    }
+
+    /// How many times the instance buffer has been uploaded to the GPU
+    /// (see [`crate::Chroma::render`]) over this `Chroma`'s lifetime.
+    pub fn instance_buffer_uploads(&self) -> u64 {
+        self.instance_buffer_uploads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoothed_fps_matches_uniform_frame_times() {
+        let mut stats = FrameStats::new();
+        for _ in 0..10 {
+            stats.record_frame(Duration::from_secs_f64(1.0 / 60.0), 0, 0);
+        }
+        assert!((stats.smoothed_fps() - 60.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn window_drops_the_oldest_frame_beyond_capacity() {
+        let mut stats = FrameStats::new();
+        for _ in 0..WINDOW_LEN {
+            stats.record_frame(Duration::from_millis(16), 0, 0);
+        }
+        stats.record_frame(Duration::from_millis(100), 0, 0);
+        assert_eq!(stats.frame_times.len(), WINDOW_LEN);
+    }
+
+    #[test]
+    fn percentiles_bracket_the_fastest_and_slowest_recorded_frame() {
+        let mut stats = FrameStats::new();
+        for ms in [10, 20, 30, 40, 50] {
+            stats.record_frame(Duration::from_millis(ms), 0, 0);
+        }
+        assert!((stats.frame_time_percentile_ms(100.0) - 50.0).abs() < 0.01);
+        assert!((stats.frame_time_percentile_ms(0.0) - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn tracks_the_latest_instance_count_and_upload_count() {
+        let mut stats = FrameStats::new();
+        stats.record_frame(Duration::from_millis(16), 42, 3);
+        assert_eq!(stats.instance_count(), 42);
+        assert_eq!(stats.instance_buffer_uploads(), 3);
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::{Duration, Instant};
+
+/// A cap on how much wall-clock time a single [`GameLoop::update`] call will
+/// try to catch up on, to avoid the "spiral of death" where a slow frame
+/// causes an ever-growing backlog of fixed updates.
+const MAX_FRAME_TIME: f32 = 0.25;
+
+/// A fixed-timestep game loop with a time accumulator, decoupling simulation
+/// rate from render rate. Independent of [`crate::Chroma`]; drive it from
+/// your own event loop and call [`GameLoop::update`] once per rendered
+/// frame.
+#[derive(Debug)]
+pub struct GameLoop {
+    target_fps: u32,
+    fixed_dt: f32,
+    accumulator: f32,
+    last_update: Option<Instant>,
+}
+
+impl GameLoop {
+    pub fn new(fps: u32) -> Self {
+        Self {
+            target_fps: fps,
+            fixed_dt: 1.0 / fps as f32,
+            accumulator: 0.0,
+            last_update: None,
+        }
+    }
+
+    pub fn target_fps(&self) -> u32 {
+        self.target_fps
+    }
+
+    /// Advances the accumulator by the time elapsed since the previous call
+    /// and invokes `f(fixed_dt)` zero or more times to catch the simulation
+    /// up to the present.
+    pub fn update<F: FnMut(f32)>(&mut self, current_time: Instant, mut f: F) {
+        let elapsed = match self.last_update {
+            Some(last) => (current_time - last).as_secs_f32(),
+            None => 0.0,
+        };
+        self.last_update = Some(current_time);
+
+        self.accumulator += elapsed.min(MAX_FRAME_TIME);
+
+        while self.accumulator >= self.fixed_dt {
+            f(self.fixed_dt);
+            self.accumulator -= self.fixed_dt;
+        }
+    }
+
+    /// How far between the last two fixed updates the current render should
+    /// be interpolated, in `[0, 1)`. Use this to blend simulation state for
+    /// smooth rendering at a render rate that doesn't match `target_fps`.
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.accumulator / self.fixed_dt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_call_never_updates() {
+        let mut game_loop = GameLoop::new(60);
+        let mut updates = 0;
+        game_loop.update(Instant::now(), |_| updates += 1);
+        assert_eq!(updates, 0);
+    }
+
+    #[test]
+    fn updates_once_per_fixed_dt_elapsed() {
+        let mut game_loop = GameLoop::new(60);
+        let start = Instant::now();
+        game_loop.update(start, |_| {});
+
+        let mut updates = 0;
+        game_loop.update(start + Duration::from_secs_f32(1.0 / 60.0), |_| updates += 1);
+        assert_eq!(updates, 1);
+    }
+
+    #[test]
+    fn catches_up_multiple_fixed_steps_in_one_call() {
+        let mut game_loop = GameLoop::new(60);
+        let start = Instant::now();
+        game_loop.update(start, |_| {});
+
+        let mut updates = 0;
+        game_loop.update(start + Duration::from_secs_f32(3.5 / 60.0), |_| updates += 1);
+        assert_eq!(updates, 3);
+    }
+
+    #[test]
+    fn clamps_a_long_stall_instead_of_spiralling() {
+        let mut game_loop = GameLoop::new(60);
+        let start = Instant::now();
+        game_loop.update(start, |_| {});
+
+        let mut updates = 0;
+        game_loop.update(start + Duration::from_secs(10), |_| updates += 1);
+        let expected = (MAX_FRAME_TIME / (1.0 / 60.0)).floor() as u32;
+        assert!(
+            (expected.saturating_sub(1)..=expected).contains(&updates),
+            "expected around {expected} updates from a {MAX_FRAME_TIME}s clamp, got {updates}"
+        );
+    }
+
+    #[test]
+    fn interpolation_alpha_reflects_the_remaining_fraction_of_a_step() {
+        let mut game_loop = GameLoop::new(60);
+        let start = Instant::now();
+        game_loop.update(start, |_| {});
+        game_loop.update(start + Duration::from_secs_f32(0.5 / 60.0), |_| {});
+
+        let alpha = game_loop.interpolation_alpha();
+        assert!((0.0..1.0).contains(&alpha));
+        assert!((alpha - 0.5).abs() < 0.001);
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::HashSet;
+
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::scaling_matrix::ScalingMatrix;
+
+/// Tracks which keys are currently held, and which were pressed or
+/// released this frame, from a stream of winit `WindowEvent`s. Also tracks
+/// cursor position, mouse buttons, and scroll delta the same way. Feed
+/// every event through [`Input::handle_event`], then call
+/// [`Input::end_frame`] once the frame's game logic has read the
+/// edge-triggered accessors (`key_pressed`/`key_released`,
+/// `mouse_button_pressed`/`mouse_button_released`, `scroll_delta`) to
+/// advance them. [`crate::Chroma::run`] does both of these automatically;
+/// construct one directly only when driving `Chroma` from a hand-rolled
+/// event loop.
+#[derive(Debug, Default)]
+pub struct Input {
+    held: HashSet<KeyCode>,
+    pressed: HashSet<KeyCode>,
+    released: HashSet<KeyCode>,
+    cursor_window_pos: Option<(f32, f32)>,
+    buttons_held: HashSet<MouseButton>,
+    buttons_pressed: HashSet<MouseButton>,
+    buttons_released: HashSet<MouseButton>,
+    scroll_delta: (f32, f32),
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates key, cursor, button, and scroll state from a single window
+    /// event. Anything else (or a key with no physical `KeyCode`, e.g. some
+    /// IME input) is ignored.
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::KeyboardInput { event: key_event, .. } => {
+                if let PhysicalKey::Code(key) = key_event.physical_key {
+                    self.record_key(key, key_event.state == ElementState::Pressed, key_event.repeat);
+                }
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.record_cursor_position((position.x as f32, position.y as f32));
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.cursor_window_pos = None;
+            }
+            WindowEvent::MouseInput { state, button, .. } => {
+                self.record_mouse_button(*button, *state == ElementState::Pressed);
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let (dx, dy) = match *delta {
+                    MouseScrollDelta::LineDelta(x, y) => (x, y),
+                    MouseScrollDelta::PixelDelta(pos) => (pos.x as f32, pos.y as f32),
+                };
+                self.scroll_delta.0 += dx;
+                self.scroll_delta.1 += dy;
+            }
+            _ => {}
+        }
+    }
+
+    /// The state transition itself, independent of winit's event types so
+    /// it can be unit tested with a synthetic sequence of calls rather
+    /// than hand-building `winit::event::KeyEvent`s (whose fields aren't
+    /// all publicly constructible).
+    pub(crate) fn record_key(&mut self, key: KeyCode, pressed: bool, repeat: bool) {
+        if pressed {
+            if !repeat && !self.held.contains(&key) {
+                self.pressed.insert(key);
+            }
+            self.held.insert(key);
+        } else {
+            self.held.remove(&key);
+            self.released.insert(key);
+        }
+    }
+
+    /// The state transition for a cursor move, independent of winit's event
+    /// types for the same testability reason as [`Input::record_key`].
+    pub(crate) fn record_cursor_position(&mut self, position: (f32, f32)) {
+        self.cursor_window_pos = Some(position);
+    }
+
+    /// The state transition for a mouse button, independent of winit's event
+    /// types for the same testability reason as [`Input::record_key`].
+    pub(crate) fn record_mouse_button(&mut self, button: MouseButton, pressed: bool) {
+        if pressed {
+            if !self.buttons_held.contains(&button) {
+                self.buttons_pressed.insert(button);
+            }
+            self.buttons_held.insert(button);
+        } else {
+            self.buttons_held.remove(&button);
+            self.buttons_released.insert(button);
+        }
+    }
+
+    /// Clears the edge-triggered `pressed`/`released` key and button sets
+    /// and the accumulated scroll delta. Call once per frame, after game
+    /// logic has read them.
+    pub fn end_frame(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+        self.buttons_pressed.clear();
+        self.buttons_released.clear();
+        self.scroll_delta = (0.0, 0.0);
+    }
+
+    /// Whether `key` is currently held down.
+    pub fn key_held(&self, key: KeyCode) -> bool {
+        self.held.contains(&key)
+    }
+
+    /// Whether `key` transitioned from up to down this frame. `false` on
+    /// OS key-repeat events for a key already held.
+    pub fn key_pressed(&self, key: KeyCode) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    /// Whether `key` transitioned from down to up this frame.
+    pub fn key_released(&self, key: KeyCode) -> bool {
+        self.released.contains(&key)
+    }
+
+    /// The cursor's last known position in window (physical pixel)
+    /// coordinates, or `None` if it hasn't moved into the window yet or has
+    /// left it (`WindowEvent::CursorLeft`). See
+    /// [`Input::cursor_pixel_position`] to translate this into backing
+    /// pixel-buffer coordinates.
+    pub fn cursor_window_position(&self) -> Option<(f32, f32)> {
+        self.cursor_window_pos
+    }
+
+    /// The cursor's position translated into backing pixel-buffer
+    /// coordinates through `matrix`, the [`ScalingMatrix`] currently in
+    /// effect (see [`crate::Chroma::window_pos_to_pixel`] for the same
+    /// translation applied to a one-off position). `None` if there's no
+    /// known cursor position; otherwise `Some(Ok(..))` while the cursor is
+    /// over the letterboxed game area, or `Some(Err(..))` with the
+    /// coordinate clamped to `virtual_size`'s bounds while it's outside it
+    /// (e.g. in the letterbox border). Passing the current matrix and
+    /// virtual size each call — rather than caching them here — is what
+    /// keeps this correct across resizes and fullscreen toggles.
+    pub fn cursor_pixel_position(
+        &self,
+        matrix: &ScalingMatrix,
+        virtual_size: (u32, u32),
+    ) -> Option<Result<(u32, u32), (i32, i32)>> {
+        self.cursor_window_pos.map(|pos| matrix.window_pos_to_pixel(pos, virtual_size))
+    }
+
+    /// Whether `button` is currently held down.
+    pub fn mouse_button_held(&self, button: MouseButton) -> bool {
+        self.buttons_held.contains(&button)
+    }
+
+    /// Whether `button` transitioned from up to down this frame.
+    pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.buttons_pressed.contains(&button)
+    }
+
+    /// Whether `button` transitioned from down to up this frame.
+    pub fn mouse_button_released(&self, button: MouseButton) -> bool {
+        self.buttons_released.contains(&button)
+    }
+
+    /// The scroll wheel delta accumulated since the last [`Input::end_frame`].
+    /// `MouseScrollDelta::PixelDelta` events are reported as-is; `LineDelta`
+    /// events are reported in lines, not pixels — scale accordingly if
+    /// mixing the two matters for a given game.
+    pub fn scroll_delta(&self) -> (f32, f32) {
+        self.scroll_delta
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn press_then_held_then_release() {
+        let mut input = Input::new();
+        input.record_key(KeyCode::Space, true, false);
+        assert!(input.key_pressed(KeyCode::Space));
+        assert!(input.key_held(KeyCode::Space));
+        assert!(!input.key_released(KeyCode::Space));
+
+        input.end_frame();
+        assert!(!input.key_pressed(KeyCode::Space));
+        assert!(input.key_held(KeyCode::Space));
+
+        input.record_key(KeyCode::Space, false, false);
+        assert!(!input.key_held(KeyCode::Space));
+        assert!(input.key_released(KeyCode::Space));
+    }
+
+    #[test]
+    fn os_repeat_events_do_not_retrigger_pressed() {
+        let mut input = Input::new();
+        input.record_key(KeyCode::KeyA, true, false);
+        input.end_frame();
+
+        input.record_key(KeyCode::KeyA, true, true);
+        assert!(!input.key_pressed(KeyCode::KeyA));
+        assert!(input.key_held(KeyCode::KeyA));
+    }
+
+    #[test]
+    fn end_frame_only_clears_edge_triggered_state() {
+        let mut input = Input::new();
+        input.record_key(KeyCode::KeyW, true, false);
+        input.end_frame();
+        assert!(input.key_held(KeyCode::KeyW));
+        assert!(!input.key_pressed(KeyCode::KeyW));
+    }
+
+    #[test]
+    fn record_cursor_position_updates_cursor_window_position() {
+        let mut input = Input::new();
+        assert_eq!(input.cursor_window_position(), None);
+        input.record_cursor_position((12.0, 34.0));
+        assert_eq!(input.cursor_window_position(), Some((12.0, 34.0)));
+    }
+
+    #[test]
+    fn mouse_button_press_then_held_then_release() {
+        let mut input = Input::new();
+        input.record_mouse_button(MouseButton::Left, true);
+        assert!(input.mouse_button_pressed(MouseButton::Left));
+        assert!(input.mouse_button_held(MouseButton::Left));
+
+        input.end_frame();
+        assert!(!input.mouse_button_pressed(MouseButton::Left));
+        assert!(input.mouse_button_held(MouseButton::Left));
+
+        input.record_mouse_button(MouseButton::Left, false);
+        assert!(!input.mouse_button_held(MouseButton::Left));
+        assert!(input.mouse_button_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn cursor_pixel_position_is_none_before_any_cursor_moved_event() {
+        let input = Input::new();
+        let matrix = ScalingMatrix::new((100, 100), (300, 200));
+        assert_eq!(input.cursor_pixel_position(&matrix, (100, 100)), None);
+    }
+
+    #[test]
+    fn cursor_pixel_position_maps_inside_the_clip_rect() {
+        let mut input = Input::new();
+        // virtual 100x100 into a 300x200 window: scale 2, clip (50, 0, 200, 200).
+        let matrix = ScalingMatrix::new((100, 100), (300, 200));
+        input.cursor_window_pos = Some((150.0, 100.0));
+        assert_eq!(input.cursor_pixel_position(&matrix, (100, 100)), Some(Ok((50, 50))));
+    }
+
+    #[test]
+    fn cursor_pixel_position_reports_outside_the_letterbox_border() {
+        let mut input = Input::new();
+        let matrix = ScalingMatrix::new((100, 100), (300, 200));
+        input.cursor_window_pos = Some((10.0, 10.0));
+        assert_eq!(input.cursor_pixel_position(&matrix, (100, 100)), Some(Err((0, 5))));
+    }
+
+    #[test]
+    fn scroll_delta_accumulates_and_resets_each_frame() {
+        let mut input = Input::new();
+        input.scroll_delta.0 += 1.0;
+        input.scroll_delta.1 += 2.0;
+        assert_eq!(input.scroll_delta(), (1.0, 2.0));
+        input.end_frame();
+        assert_eq!(input.scroll_delta(), (0.0, 0.0));
+    }
+}
+
+/// A raw keyboard press or release, as forwarded into
+/// [`crate::Chroma::process_keyboard_event`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyboardEvent {
+    pub key: winit::keyboard::KeyCode,
+    pub pressed: bool,
+}
+
+/// A raw mouse button press/release or move, as forwarded into
+/// [`crate::Chroma::process_mouse_event`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseEvent {
+    Moved { position: (f32, f32) },
+    Button { button: winit::event::MouseButton, pressed: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum InputEvent {
+    Keyboard(KeyboardEvent),
+    Mouse(MouseEvent),
+}
+
+/// A handle to an in-progress input recording, returned by
+/// [`crate::Chroma::start_recording`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RecordingHandle(pub(crate) u64);
+
+/// A captured sequence of input events, each timestamped with the frame
+/// counter at the time it was processed. Produced by
+/// [`crate::Chroma::stop_recording`] and replayed with
+/// [`crate::Chroma::play_back`].
+#[derive(Debug, Clone, Default)]
+pub struct InputRecording {
+    pub(crate) events: Vec<(u64, InputEvent)>,
+}
@@ -0,0 +1,91 @@
+/// A handle to a tile previously added to the scene via [`crate::Chroma::add_tile`].
+///
+/// Handles are opaque and only meaningful to the [`crate::Chroma`] instance that
+/// issued them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TileHandle(pub(crate) u64);
+
+/// A single sprite instance in the scene: a position and the sprite sheet index
+/// it should render.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Instance {
+    pub position: cgmath::Vector2<f32>,
+    pub index: u32,
+    pub visible: bool,
+    /// Where on the sprite `position` refers to, as a fraction of tile size
+    /// in `0..1` on each axis. `(0, 0)` is the top-left corner (the
+    /// default); `(0.5, 1.0)` is bottom-center, useful for characters.
+    pub anchor: cgmath::Vector2<f32>,
+    /// RGBA outline color, or `None` to draw no outline.
+    pub outline: Option<[f32; 4]>,
+    /// Whether to draw a simple offset drop-shadow beneath the sprite.
+    pub shadow: bool,
+    /// Vertex z, `0..1`, used by the depth/stencil pass enabled with
+    /// [`crate::Chroma::enable_depth_stencil`]. Ignored otherwise.
+    pub depth: f32,
+    /// Which debug-bounds layer this instance belongs to, set with
+    /// [`crate::Chroma::set_tile_layer`]. `0` by default. Only consulted by
+    /// [`crate::Chroma::enable_debug_bounds_on_layer`]; otherwise ignored.
+    pub layer: u8,
+}
+
+impl Instance {
+    pub(crate) fn new(position: cgmath::Vector2<f32>, index: u32) -> Self {
+        Self {
+            position,
+            index,
+            visible: true,
+            anchor: cgmath::Vector2::new(0.0, 0.0),
+            outline: None,
+            shadow: false,
+            depth: 0.0,
+            layer: 0,
+        }
+    }
+
+    pub(crate) fn with_anchor(position: cgmath::Vector2<f32>, index: u32, anchor: cgmath::Vector2<f32>) -> Self {
+        Self {
+            anchor,
+            ..Self::new(position, index)
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct InstanceRaw {
+    pub position: [f32; 2],
+    pub index: u32,
+    pub anchor: [f32; 2],
+    /// bit 0: outline enabled, bit 1: shadow enabled.
+    pub effect_flags: u32,
+    pub outline_color: [f32; 4],
+    pub depth: f32,
+    pub layer: u32,
+}
+
+const EFFECT_OUTLINE: u32 = 1 << 0;
+const EFFECT_SHADOW: u32 = 1 << 1;
+
+impl From<Instance> for InstanceRaw {
+    fn from(instance: Instance) -> Self {
+        let mut effect_flags = 0;
+        if instance.outline.is_some() {
+            effect_flags |= EFFECT_OUTLINE;
+        }
+        if instance.shadow {
+            effect_flags |= EFFECT_SHADOW;
+        }
+
+        Self {
+            position: instance.position.into(),
+            index: instance.index,
+            anchor: instance.anchor.into(),
+            effect_flags,
+            outline_color: instance.outline.unwrap_or([0.0; 4]),
+            depth: instance.depth,
+            layer: instance.layer as u32,
+        }
+    }
+}
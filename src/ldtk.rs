@@ -0,0 +1,141 @@
+//! Minimal [LDtk](https://ldtk.io) level importer. Reads the tile layers of
+//! one level out of an exported project's JSON and turns them into scene
+//! tiles via [`crate::Chroma::load_ldtk`].
+//!
+//! IntGrid layers (LDtk's collision/gameplay-data layers, as opposed to
+//! visual tile layers) aren't imported yet — only `Tiles` and `AutoLayer`
+//! layers are.
+
+use crate::{ChromaError, TileHandle};
+
+/// The tiles [`crate::Chroma::load_ldtk`] added for one level, grouped so
+/// they can be removed together when the level unloads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileMapHandle(pub(crate) Vec<TileHandle>);
+
+impl TileMapHandle {
+    /// The individual tile handles that make up this level, in the order
+    /// they were created.
+    pub fn tiles(&self) -> &[TileHandle] {
+        &self.0
+    }
+}
+
+/// Maps an LDtk tile id (local to the tileset a layer paints from) to a
+/// sprite-sheet index, for callers whose sheet doesn't start that tileset
+/// at index `0` (e.g. several small tilesets packed into one shared atlas).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TilesetMapping {
+    base_index: u32,
+}
+
+impl TilesetMapping {
+    /// `base_index` is added to every tile id this mapping resolves.
+    pub fn new(base_index: u32) -> Self {
+        Self { base_index }
+    }
+
+    pub(crate) fn sprite_index(&self, tile_id: u32) -> u32 {
+        self.base_index + tile_id
+    }
+}
+
+/// Finds the level named `level_id` in a parsed LDtk project.
+pub(crate) fn find_level<'a>(
+    project: &'a serde_json::Value,
+    level_id: &str,
+) -> Result<&'a serde_json::Value, ChromaError> {
+    project["levels"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|level| level["identifier"].as_str() == Some(level_id))
+        .ok_or_else(|| ChromaError::UnknownLdtkLevel(level_id.to_string()))
+}
+
+/// Extracts `(position, tile_id)` pairs from every `Tiles`/`AutoLayer` layer
+/// instance in `level`, in layer order.
+pub(crate) fn tile_placements(level: &serde_json::Value) -> Vec<((f32, f32), u32)> {
+    let mut placements = Vec::new();
+    for layer in level["layerInstances"].as_array().into_iter().flatten() {
+        let is_tile_layer = matches!(layer["__type"].as_str(), Some("Tiles") | Some("AutoLayer"));
+        if !is_tile_layer {
+            continue;
+        }
+
+        let tiles = layer["gridTiles"]
+            .as_array()
+            .filter(|tiles| !tiles.is_empty())
+            .or_else(|| layer["autoLayerTiles"].as_array())
+            .into_iter()
+            .flatten();
+
+        for tile in tiles {
+            let Some(px) = tile["px"].as_array() else { continue };
+            let (Some(x), Some(y)) = (px.first().and_then(|v| v.as_f64()), px.get(1).and_then(|v| v.as_f64())) else {
+                continue;
+            };
+            let tile_id = tile["t"].as_u64().unwrap_or(0) as u32;
+            placements.push(((x as f32, y as f32), tile_id));
+        }
+    }
+    placements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_project() -> serde_json::Value {
+        serde_json::json!({
+            "levels": [
+                {
+                    "identifier": "Level_0",
+                    "layerInstances": [
+                        {
+                            "__type": "IntGrid",
+                            "gridTiles": [{"px": [0, 0], "t": 99}]
+                        },
+                        {
+                            "__type": "Tiles",
+                            "gridTiles": [
+                                {"px": [0, 0], "t": 1},
+                                {"px": [16, 0], "t": 2}
+                            ]
+                        }
+                    ]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn finds_level_by_identifier() {
+        let project = sample_project();
+        let level = find_level(&project, "Level_0").unwrap();
+        assert_eq!(level["identifier"], "Level_0");
+    }
+
+    #[test]
+    fn errors_on_unknown_level() {
+        let project = sample_project();
+        assert!(matches!(
+            find_level(&project, "Nope"),
+            Err(ChromaError::UnknownLdtkLevel(name)) if name == "Nope"
+        ));
+    }
+
+    #[test]
+    fn tile_placements_skips_non_tile_layers() {
+        let project = sample_project();
+        let level = find_level(&project, "Level_0").unwrap();
+        let placements = tile_placements(level);
+        assert_eq!(placements, vec![((0.0, 0.0), 1), ((16.0, 0.0), 2)]);
+    }
+
+    #[test]
+    fn tileset_mapping_offsets_tile_ids() {
+        let mapping = TilesetMapping::new(100);
+        assert_eq!(mapping.sprite_index(1), 101);
+    }
+}
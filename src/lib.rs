@@ -0,0 +1,1835 @@
+//! chroma is a tiny pixel-art 2D game engine built on top of `wgpu` and
+//! `winit`. Games render to a fixed virtual resolution which chroma
+//! letterboxes and integer-scales into the actual window.
+
+mod atlas;
+mod builder;
+mod camera;
+mod collision_grid;
+mod collision_mask;
+mod custom_pass;
+mod debug_bounds;
+mod debug_grid;
+#[cfg(feature = "hecs")]
+mod ecs;
+mod error;
+mod events;
+mod fps_tracker;
+mod frame_limiter;
+mod frame_stats;
+mod game_loop;
+mod input;
+mod instance;
+mod ldtk;
+mod lighting;
+#[cfg(feature = "pathfinding")]
+mod path_grid;
+mod pixels;
+mod present_mode;
+mod rect;
+mod render_pause;
+mod renderers;
+mod scaling_matrix;
+mod surface_format;
+mod texture;
+mod tile_streaming;
+mod transition;
+#[cfg(all(feature = "async-loading", target_arch = "wasm32"))]
+mod wasm_fetch;
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use debug_grid::DebugGrid;
+use fps_tracker::FpsTracker;
+use frame_limiter::FrameLimiter;
+use frame_stats::FrameStats;
+use input::InputEvent;
+use instance::{Instance, InstanceRaw};
+use lighting::LightingPass;
+use render_pause::RenderPause;
+use renderers::SpriteRenderer;
+use transition::TransitionState;
+
+pub use atlas::{AtlasError, AtlasRegion, SpriteAtlas};
+pub use builder::{ChromaBuilder, create_window};
+pub use camera::CameraSnap;
+pub use collision_grid::{CollisionFlags, CollisionGrid};
+pub use collision_mask::CollisionMask;
+pub use custom_pass::CustomRenderPass;
+#[cfg(feature = "hecs")]
+pub use ecs::{ChromaRenderer, IntoVirtualPixel, sync_chroma_transforms};
+pub use error::ChromaError;
+#[cfg(feature = "serde")]
+pub use error::SerializeError;
+pub use events::{ChromaEvent, EventQueue};
+pub use fps_tracker::FpsTracker;
+pub use frame_stats::FrameStats;
+pub use game_loop::GameLoop;
+pub use input::{Input, InputRecording, KeyboardEvent, MouseEvent, RecordingHandle};
+pub use instance::TileHandle;
+pub use ldtk::{TileMapHandle, TilesetMapping};
+pub use lighting::{Light, LightHandle};
+#[cfg(feature = "pathfinding")]
+pub use path_grid::PathGrid;
+pub use pixels::SurfaceSize;
+pub use rect::Rect;
+pub use scaling_matrix::ScreenRotation;
+pub use texture::TextureHandle;
+#[cfg(feature = "async-loading")]
+pub use texture::TextureLoadFuture;
+use texture::StoredTexture;
+pub use tile_streaming::StreamingTileMap;
+pub use transition::{Transition, WipeDirection};
+
+/// The main engine handle. Owns the window, the GPU surface, and the current
+/// set of sprite instances.
+pub struct Chroma {
+    window: winit::window::Window,
+    surface: wgpu::Surface<'static>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    surface_format: wgpu::TextureFormat,
+    present_mode: wgpu::PresentMode,
+    virtual_size: (u32, u32),
+    renderer: SpriteRenderer,
+    instances: HashMap<u64, Instance>,
+    ephemeral_instances: Vec<Instance>,
+    next_handle: u64,
+    frame_counter: u64,
+    recording: Option<Vec<(u64, InputEvent)>>,
+    next_recording_handle: u64,
+    playback_active: bool,
+    camera: cgmath::Vector2<f32>,
+    camera_snap: CameraSnap,
+    lighting: LightingPass,
+    atlas: Option<SpriteAtlas>,
+    transition: Option<TransitionState>,
+    collision_grid: Option<CollisionGrid>,
+    tile_size: (u32, u32),
+    #[cfg(feature = "debug-font")]
+    debug_text: Vec<(cgmath::Vector2<f32>, String)>,
+    fps_tracker: FpsTracker,
+    frame_stats: FrameStats,
+    events: EventQueue<ChromaEvent>,
+    custom_passes: Vec<Box<dyn CustomRenderPass>>,
+    textures: HashMap<u64, StoredTexture>,
+    next_texture_handle: u64,
+    active_sprite_sheet: Option<TextureHandle>,
+    texture_cache: HashMap<u64, TextureHandle>,
+    start_time: instant::Instant,
+    last_frame_time: instant::Instant,
+    frame_delta: std::time::Duration,
+    render_pause: RenderPause,
+    frame_limiter: FrameLimiter,
+    last_present_time: instant::Instant,
+    input: Input,
+}
+
+/// `window`, `surface`, `device`, `queue`, and `renderer` don't implement
+/// `Debug` (the last carries a `Box<dyn Fn>` render hook on top of its wgpu
+/// handles), so this prints a placeholder for each and reports collections
+/// by their length rather than their contents.
+impl std::fmt::Debug for Chroma {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Chroma")
+            .field("window", &"<winit::window::Window>")
+            .field("surface", &"<wgpu::Surface>")
+            .field("device", &"<wgpu::Device>")
+            .field("queue", &"<wgpu::Queue>")
+            .field("surface_format", &self.surface_format)
+            .field("present_mode", &self.present_mode)
+            .field("virtual_size", &self.virtual_size)
+            .field("renderer", &"<SpriteRenderer>")
+            .field("instance_count", &self.instances.len())
+            .field("ephemeral_instance_count", &self.ephemeral_instances.len())
+            .field("frame_counter", &self.frame_counter)
+            .field("recording_active", &self.recording.is_some())
+            .field("playback_active", &self.playback_active)
+            .field("camera", &self.camera)
+            .field("camera_snap", &self.camera_snap)
+            .field("atlas", &self.atlas.is_some())
+            .field("transition_active", &self.transition.is_some())
+            .field("collision_grid", &self.collision_grid)
+            .field("tile_size", &self.tile_size)
+            .field("fps_tracker", &self.fps_tracker)
+            .field("frame_stats", &self.frame_stats)
+            .field("events", &self.events)
+            .field("custom_pass_count", &self.custom_passes.len())
+            .field("texture_count", &self.textures.len())
+            .field("active_sprite_sheet", &self.active_sprite_sheet)
+            .field("frame_delta", &self.frame_delta)
+            .field("render_pause", &self.render_pause)
+            .field("frame_limiter", &self.frame_limiter)
+            .field("input", &self.input)
+            .finish()
+    }
+}
+
+impl Chroma {
+    pub(crate) fn new(
+        window: winit::window::Window,
+        virtual_size: (u32, u32),
+    ) -> Result<Self, ChromaError> {
+        Self::new_with_features(window, virtual_size, wgpu::Features::empty())
+    }
+
+    /// Like the plain constructor, but requests `features` from the GPU
+    /// adapter — timestamp queries, push constants, texture binding arrays,
+    /// and other advanced `wgpu::Features` a downstream render pass might
+    /// need. Errors with [`ChromaError::FeatureNotSupported`] if the
+    /// adapter doesn't support everything requested.
+    pub(crate) fn new_with_features(
+        window: winit::window::Window,
+        virtual_size: (u32, u32),
+        features: wgpu::Features,
+    ) -> Result<Self, ChromaError> {
+        let instance = wgpu::Instance::default();
+        let surface = instance.create_surface(&window)?;
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            compatible_surface: Some(&surface),
+            ..Default::default()
+        }))
+        .ok_or(ChromaError::AdapterNotFound)?;
+
+        if !adapter.features().contains(features) {
+            return Err(ChromaError::FeatureNotSupported(features));
+        }
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                features,
+                ..Default::default()
+            },
+            None,
+        ))?;
+
+        let window_size = window.inner_size();
+        let capabilities = surface.get_capabilities(&adapter);
+        let present_mode = present_mode::choose_present_mode(&capabilities.present_modes);
+        let surface_format = surface_format::choose_surface_format(&capabilities.formats);
+
+        surface.configure(
+            &device,
+            &wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: surface_format,
+                width: window_size.width,
+                height: window_size.height,
+                present_mode,
+                alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                view_formats: vec![],
+                desired_maximum_frame_latency: 2,
+            },
+        );
+
+        let renderer = SpriteRenderer::new(virtual_size, (window_size.width, window_size.height));
+
+        Ok(Self {
+            window,
+            surface,
+            device,
+            queue,
+            surface_format,
+            present_mode,
+            virtual_size,
+            renderer,
+            instances: HashMap::new(),
+            ephemeral_instances: Vec::new(),
+            next_handle: 0,
+            frame_counter: 0,
+            recording: None,
+            next_recording_handle: 0,
+            playback_active: false,
+            camera: cgmath::Vector2::new(0.0, 0.0),
+            camera_snap: CameraSnap::default(),
+            lighting: LightingPass::new(),
+            atlas: None,
+            transition: None,
+            collision_grid: None,
+            tile_size: (16, 16),
+            #[cfg(feature = "debug-font")]
+            debug_text: Vec::new(),
+            fps_tracker: FpsTracker::new(),
+            frame_stats: FrameStats::new(),
+            events: EventQueue::new(),
+            custom_passes: Vec::new(),
+            textures: HashMap::new(),
+            texture_cache: HashMap::new(),
+            next_texture_handle: 0,
+            active_sprite_sheet: None,
+            start_time: instant::Instant::now(),
+            last_frame_time: instant::Instant::now(),
+            frame_delta: std::time::Duration::ZERO,
+            render_pause: RenderPause::default(),
+            frame_limiter: FrameLimiter::default(),
+            last_present_time: instant::Instant::now(),
+            input: Input::new(),
+        })
+    }
+
+    /// Builds a [`Chroma`] from wgpu objects the caller already created,
+    /// skipping adapter/device/surface creation entirely.
+    ///
+    /// For winit 0.29+'s `ApplicationHandler` lifecycle, where the window
+    /// (and anything depending on it, like the surface) can only be created
+    /// inside `resumed()` rather than up front the way [`ChromaBuilder`]
+    /// assumes. Callers on that lifecycle build their own `wgpu::Instance`,
+    /// request an adapter/device, create the surface from `window`, and
+    /// hand all of it here instead of going through [`ChromaBuilder`].
+    ///
+    /// `surface_capabilities` (from `surface.get_capabilities(&adapter)`)
+    /// picks the surface format and present mode the same way
+    /// [`Chroma::new_with_features`] does, and this configures `surface`
+    /// for `window`'s current size before returning.
+    pub fn new_from_surface(
+        window: winit::window::Window,
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        surface: wgpu::Surface<'static>,
+        surface_capabilities: wgpu::SurfaceCapabilities,
+        virtual_size: (u32, u32),
+    ) -> Result<Self, ChromaError> {
+        let window_size = window.inner_size();
+        let present_mode = present_mode::choose_present_mode(&surface_capabilities.present_modes);
+        let surface_format = surface_format::choose_surface_format(&surface_capabilities.formats);
+
+        surface.configure(
+            &device,
+            &wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: surface_format,
+                width: window_size.width,
+                height: window_size.height,
+                present_mode,
+                alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                view_formats: vec![],
+                desired_maximum_frame_latency: 2,
+            },
+        );
+
+        let renderer = SpriteRenderer::new(virtual_size, (window_size.width, window_size.height));
+
+        Ok(Self {
+            window,
+            surface,
+            device,
+            queue,
+            surface_format,
+            present_mode,
+            virtual_size,
+            renderer,
+            instances: HashMap::new(),
+            ephemeral_instances: Vec::new(),
+            next_handle: 0,
+            frame_counter: 0,
+            recording: None,
+            next_recording_handle: 0,
+            playback_active: false,
+            camera: cgmath::Vector2::new(0.0, 0.0),
+            camera_snap: CameraSnap::default(),
+            lighting: LightingPass::new(),
+            atlas: None,
+            transition: None,
+            collision_grid: None,
+            tile_size: (16, 16),
+            #[cfg(feature = "debug-font")]
+            debug_text: Vec::new(),
+            fps_tracker: FpsTracker::new(),
+            frame_stats: FrameStats::new(),
+            events: EventQueue::new(),
+            custom_passes: Vec::new(),
+            textures: HashMap::new(),
+            texture_cache: HashMap::new(),
+            next_texture_handle: 0,
+            active_sprite_sheet: None,
+            start_time: instant::Instant::now(),
+            last_frame_time: instant::Instant::now(),
+            frame_delta: std::time::Duration::ZERO,
+            render_pause: RenderPause::default(),
+            frame_limiter: FrameLimiter::default(),
+            last_present_time: instant::Instant::now(),
+            input: Input::new(),
+        })
+    }
+
+    /// Starts a scene transition, replacing any transition already in
+    /// progress. Advance it each frame with [`Chroma::update_transition`]
+    /// and check [`Chroma::transition_finished`] to know when to swap
+    /// scenes.
+    pub fn start_transition(&mut self, transition: Transition) {
+        self.transition = Some(TransitionState::new(transition));
+    }
+
+    /// Advances the active transition, if any, by `dt` seconds.
+    pub fn update_transition(&mut self, dt: f32) {
+        if let Some(transition) = &mut self.transition {
+            transition.update(dt);
+        }
+    }
+
+    /// Whether the active transition has reached its end. `false` if no
+    /// transition is in progress.
+    pub fn transition_finished(&self) -> bool {
+        self.transition.as_ref().is_some_and(|t| t.finished())
+    }
+
+    /// Checks that an atlas's sheet bytes decode as an image and remembers
+    /// its named regions so [`Chroma::add_sprite_by_name`] can look sprites
+    /// up by name instead of a raw sheet index. No GPU texture is created
+    /// from the sheet and no per-region UV data is built.
+    pub fn load_atlas(&mut self, atlas: SpriteAtlas) -> Result<(), ChromaError> {
+        // The decoded image is dropped; decoding only validates the bytes.
+        image::load_from_memory(&atlas.image_bytes)?;
+        self.atlas = Some(atlas);
+        Ok(())
+    }
+
+    /// Imports the level named `level_id` from an exported LDtk project's
+    /// JSON, adding a tile for every cell of its `Tiles`/`AutoLayer` layers
+    /// via [`Chroma::add_tile`]. `mapping` resolves each layer's LDtk tile
+    /// ids to sprite-sheet indices. Returns the tiles that were added,
+    /// grouped as a [`TileMapHandle`] so the level can be torn down as a
+    /// unit. IntGrid layers (LDtk's collision/gameplay-data layers) aren't
+    /// imported yet — only visual tile layers are.
+    pub fn load_ldtk(
+        &mut self,
+        json: &[u8],
+        level_id: &str,
+        mapping: &TilesetMapping,
+    ) -> Result<TileMapHandle, ChromaError> {
+        let project: serde_json::Value = serde_json::from_slice(json)?;
+        let level = ldtk::find_level(&project, level_id)?;
+
+        let tiles = ldtk::tile_placements(level)
+            .into_iter()
+            .map(|((x, y), tile_id)| {
+                self.add_tile(cgmath::Vector2::new(x, y), mapping.sprite_index(tile_id))
+            })
+            .collect();
+
+        Ok(TileMapHandle(tiles))
+    }
+
+    /// Adds a sprite by its name in the currently loaded atlas. Errors if
+    /// no atlas is loaded or the name isn't a region in it.
+    pub fn add_sprite_by_name(
+        &mut self,
+        name: &str,
+        position: cgmath::Vector2<f32>,
+    ) -> Result<TileHandle, ChromaError> {
+        let region = self
+            .atlas
+            .as_ref()
+            .and_then(|atlas| atlas.region(name))
+            .ok_or(ChromaError::UnknownAtlasRegion)?;
+
+        // The sprite index encodes the region's top-left texel offset in
+        // the sheet until the renderer gains full per-region UV support.
+        let index = region.y * self.virtual_size.0 + region.x;
+        Ok(self.add_tile(position, index))
+    }
+
+    /// Registers an additive point light for the optional lighting overlay
+    /// pass. Lights move with the camera, the same as tiles. The overlay is
+    /// only active once at least one light has been added or
+    /// [`Chroma::set_ambient_light`] has been called.
+    pub fn add_light(&mut self, position: cgmath::Vector2<f32>, radius: f32, color: [f32; 3]) -> LightHandle {
+        self.lighting.add_light(Light { position, radius, color })
+    }
+
+    pub fn remove_light(&mut self, handle: LightHandle) {
+        self.lighting.remove_light(handle);
+    }
+
+    /// Sets the ambient light level (`0..1`) applied everywhere before
+    /// additive point lights are blended in.
+    pub fn set_ambient_light(&mut self, ambient: f32) {
+        self.lighting.set_ambient(ambient);
+    }
+
+    /// Moves the camera to `position`, in virtual pixels. How fractional
+    /// offsets are handled is controlled by [`Chroma::set_camera_snap`].
+    pub fn set_camera_position(&mut self, position: cgmath::Vector2<f32>) {
+        self.camera = position;
+    }
+
+    /// Sets how fractional camera offsets are rounded before upload. See
+    /// [`CameraSnap`] for the tradeoffs of each mode.
+    pub fn set_camera_snap(&mut self, snap: CameraSnap) {
+        self.camera_snap = snap;
+    }
+
+    /// Rotates the scaled game image within the window, for
+    /// portrait-orientation games on a landscape display (or vice versa).
+    pub fn set_screen_rotation(&mut self, rotation: ScreenRotation) {
+        let window_size = self.window.inner_size();
+        self.renderer
+            .set_rotation(rotation, self.virtual_size, (window_size.width, window_size.height));
+    }
+
+    /// Forwards a keyboard event into chroma's input handling, updating
+    /// [`Chroma::input`] the same way [`Input::record_key`] does. Ignored
+    /// while a recording is being played back with [`Chroma::play_back`].
+    /// Buffered if a recording is in progress.
+    pub fn process_keyboard_event(&mut self, event: input::KeyboardEvent) {
+        if self.playback_active {
+            return;
+        }
+        self.input.record_key(event.key, event.pressed, false);
+        self.record_event(InputEvent::Keyboard(event));
+    }
+
+    /// Forwards a mouse event into chroma's input handling, updating
+    /// [`Chroma::input`] the same way [`Input::record_cursor_position`]/
+    /// [`Input::record_mouse_button`] do. Ignored while a recording is being
+    /// played back with [`Chroma::play_back`]. Buffered if a recording is in
+    /// progress.
+    pub fn process_mouse_event(&mut self, event: input::MouseEvent) {
+        if self.playback_active {
+            return;
+        }
+        match event {
+            MouseEvent::Moved { position } => self.input.record_cursor_position(position),
+            MouseEvent::Button { button, pressed } => self.input.record_mouse_button(button, pressed),
+        }
+        self.record_event(InputEvent::Mouse(event));
+    }
+
+    fn record_event(&mut self, event: InputEvent) {
+        if let Some(buffer) = &mut self.recording {
+            buffer.push((self.frame_counter, event));
+        }
+    }
+
+    /// Begins buffering every processed keyboard and mouse event, tagged
+    /// with the frame counter at the time it occurred.
+    pub fn start_recording(&mut self) -> input::RecordingHandle {
+        self.recording = Some(Vec::new());
+        let handle = input::RecordingHandle(self.next_recording_handle);
+        self.next_recording_handle += 1;
+        handle
+    }
+
+    /// Stops the current recording and returns everything captured since
+    /// [`Chroma::start_recording`].
+    pub fn stop_recording(&mut self, _handle: input::RecordingHandle) -> input::InputRecording {
+        input::InputRecording {
+            events: self.recording.take().unwrap_or_default(),
+        }
+    }
+
+    /// The [`Input`] state driven by [`Chroma::process_keyboard_event`]/
+    /// [`Chroma::process_mouse_event`] — which [`Chroma::run`] calls
+    /// automatically for every live event, so this reflects live input
+    /// there too — and by [`Chroma::play_back`] while a recording is being
+    /// replayed. Note it only sees non-repeat key presses and cursor
+    /// moves/button presses, the same subset [`input::KeyboardEvent`]/
+    /// [`input::MouseEvent`] can represent; scroll and the cursor leaving
+    /// the window aren't tracked here (see [`Chroma::run`]'s own `&Input`
+    /// argument for full fidelity against live `winit` events).
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    /// Replays a previously captured recording in order, driving
+    /// [`Chroma::input`] exactly as live events would via
+    /// [`Input::record_key`]/[`Input::record_mouse_button`], while blocking
+    /// [`Chroma::process_keyboard_event`] and [`Chroma::process_mouse_event`]
+    /// from accepting live input for the duration of the call — so a test
+    /// can capture a recording once, then replay it and assert on
+    /// [`Chroma::input`] deterministically.
+    pub fn play_back(&mut self, recording: &input::InputRecording) {
+        self.playback_active = true;
+        for (_frame, event) in &recording.events {
+            match event {
+                InputEvent::Keyboard(KeyboardEvent { key, pressed }) => {
+                    self.input.record_key(*key, *pressed, false);
+                }
+                InputEvent::Mouse(MouseEvent::Moved { position }) => {
+                    self.input.record_cursor_position(*position);
+                }
+                InputEvent::Mouse(MouseEvent::Button { button, pressed }) => {
+                    self.input.record_mouse_button(*button, *pressed);
+                }
+            }
+        }
+        self.playback_active = false;
+    }
+
+    /// Adds a sprite to the scene at `position`, displaying sprite sheet
+    /// index `index`. Returns a handle that can later be used to move,
+    /// swap, or remove it.
+    #[must_use = "dropping the handle leaves the tile in the scene with no way to move or remove it later"]
+    pub fn add_tile(&mut self, position: cgmath::Vector2<f32>, index: u32) -> TileHandle {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.instances.insert(handle, Instance::new(position, index));
+        let handle = TileHandle(handle);
+        self.events.push(ChromaEvent::TileAdded(handle));
+        handle
+    }
+
+    /// Like [`Chroma::add_tile`], but `position` refers to `anchor` on the
+    /// sprite (a fraction of tile size in `0..1` on each axis) rather than
+    /// the top-left corner. `(0.5, 1.0)` is a common choice for characters
+    /// (bottom-center, i.e. their feet).
+    #[must_use = "dropping the handle leaves the tile in the scene with no way to move or remove it later"]
+    pub fn add_tile_anchored(
+        &mut self,
+        position: cgmath::Vector2<f32>,
+        index: u32,
+        anchor: cgmath::Vector2<f32>,
+    ) -> TileHandle {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.instances
+            .insert(handle, Instance::with_anchor(position, index, anchor));
+        let handle = TileHandle(handle);
+        self.events.push(ChromaEvent::TileAdded(handle));
+        handle
+    }
+
+    /// Replaces the entire scene in one operation, discarding all existing
+    /// tiles and their handles. Equivalent to clearing and calling
+    /// [`Chroma::add_tile`] for each entry, but avoids allocating a fresh
+    /// buffer per tile, and pushes no [`ChromaEvent::TileAdded`] events.
+    pub fn set_instances(&mut self, instances: &[(cgmath::Vector2<f32>, u32)]) {
+        self.instances.clear();
+        self.next_handle = 0;
+        for &(position, index) in instances {
+            self.instances.insert(self.next_handle, Instance::new(position, index));
+            self.next_handle += 1;
+        }
+    }
+
+    /// Adds `delta` (in virtual pixels) to every tile's position in one
+    /// pass. Useful for scrolling the whole world when the camera moves,
+    /// avoiding N individual [`Chroma::move_tile`] calls.
+    pub fn translate_all_tiles(&mut self, delta: cgmath::Vector2<f32>) {
+        for instance in self.instances.values_mut() {
+            instance.position += delta;
+        }
+    }
+
+    /// Shows or hides a tile without removing it, avoiding handle churn for
+    /// effects like flashing invincibility. Hidden tiles are skipped when
+    /// building the GPU instance buffer.
+    pub fn set_tile_visible(&mut self, handle: TileHandle, visible: bool) {
+        if let Some(instance) = self.instances.get_mut(&handle.0) {
+            instance.visible = visible;
+        }
+    }
+
+    /// Sets the tile-based collision data used by [`Chroma::tile_at_position`]
+    /// consumers, stored separately from the visual tiles added via
+    /// [`Chroma::add_tile`] so collision layout can change independently of
+    /// what's on screen.
+    pub fn set_collision_grid(&mut self, grid: CollisionGrid) {
+        self.collision_grid = Some(grid);
+    }
+
+    /// The collision grid set via [`Chroma::set_collision_grid`], if any.
+    pub fn collision_grid(&self) -> Option<&CollisionGrid> {
+        self.collision_grid.as_ref()
+    }
+
+    /// Sets the tile size, in virtual pixels, used to convert between
+    /// world positions and collision-grid coordinates in
+    /// [`Chroma::tile_at_position`]. `(16, 16)` by default.
+    pub fn set_tile_size(&mut self, tile_size: (u32, u32)) {
+        self.tile_size = tile_size;
+    }
+
+    /// Converts a virtual-pixel position to the collision-grid tile
+    /// coordinate that contains it, using the size set via
+    /// [`Chroma::set_tile_size`]. Negative positions clamp to `(0, 0)`.
+    pub fn tile_at_position(&self, pos: cgmath::Vector2<f32>) -> (u32, u32) {
+        let x = (pos.x.max(0.0) / self.tile_size.0 as f32) as u32;
+        let y = (pos.y.max(0.0) / self.tile_size.1 as f32) as u32;
+        (x, y)
+    }
+
+    /// Pushes a scissor rect, in backing-texture pixel coordinates, that
+    /// clips subsequent sprite draws until popped with [`Chroma::pop_clip`].
+    /// Nested clips intersect with the currently active one.
+    pub fn push_clip(&mut self, rect: (u32, u32, u32, u32)) {
+        self.renderer.push_clip(rect, self.virtual_size);
+    }
+
+    /// Pops the most recently pushed clip rect.
+    pub fn pop_clip(&mut self) {
+        self.renderer.pop_clip();
+    }
+
+    /// Returns the handles of every tile whose bounding box (`position` ±
+    /// `tolerance` on each axis) contains `position`. Useful for
+    /// click-to-select or simple overlap checks.
+    pub fn find_tiles_at(&self, position: cgmath::Vector2<f32>, tolerance: f32) -> Vec<TileHandle> {
+        self.instances
+            .iter()
+            .filter(|(_, instance)| {
+                (instance.position.x - position.x).abs() <= tolerance
+                    && (instance.position.y - position.y).abs() <= tolerance
+            })
+            .map(|(&id, _)| TileHandle(id))
+            .collect()
+    }
+
+    /// Whether `handle`'s bounding box intersects the current camera
+    /// viewport, in virtual pixels. Accounts for the tile's anchor and the
+    /// active camera offset, so it stays correct as the camera scrolls.
+    /// Returns `false` for a handle that no longer refers to a live tile.
+    ///
+    /// Useful for manual culling or LOD: skip expensive per-frame work, or
+    /// swap to a simplified sprite, for tiles this returns `false` for.
+    pub fn is_tile_visible(&self, handle: TileHandle) -> bool {
+        let Some(instance) = self.instances.get(&handle.0) else {
+            return false;
+        };
+
+        let (tile_width, tile_height) = self.tile_size;
+        let screen_position = instance.position - self.camera;
+        let min = screen_position
+            - cgmath::Vector2::new(
+                instance.anchor.x * tile_width as f32,
+                instance.anchor.y * tile_height as f32,
+            );
+        let max = min + cgmath::Vector2::new(tile_width as f32, tile_height as f32);
+
+        let viewport_width = self.virtual_size.0 as f32;
+        let viewport_height = self.virtual_size.1 as f32;
+
+        max.x > 0.0 && min.x < viewport_width && max.y > 0.0 && min.y < viewport_height
+    }
+
+    /// Sets or clears a 1px outline drawn around a tile's opaque pixels.
+    /// `None` draws no outline.
+    pub fn set_tile_outline(&mut self, handle: TileHandle, outline: Option<[f32; 4]>) {
+        if let Some(instance) = self.instances.get_mut(&handle.0) {
+            instance.outline = outline;
+        }
+    }
+
+    /// Enables or disables a simple offset drop-shadow beneath a tile.
+    pub fn set_tile_shadow(&mut self, handle: TileHandle, shadow: bool) {
+        if let Some(instance) = self.instances.get_mut(&handle.0) {
+            instance.shadow = shadow;
+        }
+    }
+
+    /// Sets a tile's vertex z (`0..1`), used by the depth/stencil pass
+    /// enabled with [`Chroma::enable_depth_stencil`]. Ignored otherwise.
+    pub fn set_tile_depth(&mut self, handle: TileHandle, depth: f32) {
+        if let Some(instance) = self.instances.get_mut(&handle.0) {
+            instance.depth = depth;
+        }
+    }
+
+    /// Sets which debug-bounds layer a tile belongs to, consulted by
+    /// [`Chroma::enable_debug_bounds_on_layer`]. `0` by default.
+    pub fn set_tile_layer(&mut self, handle: TileHandle, layer: u8) {
+        if let Some(instance) = self.instances.get_mut(&handle.0) {
+            instance.layer = layer;
+        }
+    }
+
+    /// Creates a depth/stencil texture at virtual resolution and enables
+    /// per-instance depth testing, for effects like masking sprites behind
+    /// walls or clipping sprites at a water line. Set each tile's depth
+    /// with [`Chroma::set_tile_depth`].
+    pub fn enable_depth_stencil(&mut self, format: wgpu::TextureFormat) {
+        self.renderer.enable_depth_stencil(&self.device, format, self.virtual_size);
+    }
+
+    /// Clears the depth/stencil buffer on the next render pass. A no-op if
+    /// [`Chroma::enable_depth_stencil`] hasn't been called.
+    pub fn clear_depth(&mut self) {
+        self.renderer.clear_depth();
+    }
+
+    /// Overlays a grid of `grid_size`-pixel squares (borders only) on the
+    /// virtual framebuffer, drawn on top of every sprite instance but below
+    /// any UI layer. Useful for checking sprite alignment during
+    /// development. Replaces any grid already enabled; turn it off with
+    /// [`Chroma::toggle_debug_grid`].
+    pub fn enable_debug_grid(&mut self, grid_size: u32, color: [f32; 4]) {
+        self.renderer.set_debug_grid(Some(DebugGrid::new(grid_size, color)));
+    }
+
+    /// Toggles the debug grid on or off without forgetting its size and
+    /// color. A no-op if [`Chroma::enable_debug_grid`] hasn't been called.
+    pub fn toggle_debug_grid(&mut self) {
+        self.renderer.toggle_debug_grid();
+    }
+
+    /// Outlines every instance's virtual-pixel bounding box with a
+    /// one-pixel rect in `color`, drawn after all sprites are composited
+    /// and before the upscale pass so the outlines land on exact virtual
+    /// pixels. Layers with a color set via
+    /// [`Chroma::enable_debug_bounds_on_layer`] use that instead.
+    pub fn enable_debug_bounds(&mut self, color: [f32; 4]) {
+        self.renderer.set_debug_bounds_default(Some(color));
+    }
+
+    /// Outlines only instances on `layer` (see [`Chroma::set_tile_layer`])
+    /// with `color`, overriding the default from
+    /// [`Chroma::enable_debug_bounds`] for that layer.
+    pub fn enable_debug_bounds_on_layer(&mut self, layer: u8, color: [f32; 4]) {
+        self.renderer.set_debug_bounds_layer(layer, color);
+    }
+
+    /// Registers `f` to run inside the sprite render pass, after the
+    /// instanced sprite draw call but before the pass ends, for drawing
+    /// custom geometry (lines, outlines, extra meshes) into the same pass —
+    /// so it composites with sprites before the upscale pass, without
+    /// forking [`Chroma::render`]. Replaces any previously registered hook.
+    pub fn with_sprite_pass_hook(
+        &mut self,
+        f: impl Fn(&wgpu::Device, &wgpu::Queue, &mut wgpu::RenderPass<'_>) + 'static,
+    ) {
+        self.renderer.set_sprite_pass_hook(f);
+    }
+
+    /// Registers a whole extra render pass, run in registration order after
+    /// the sprite pass and before the frame is submitted. See
+    /// [`CustomRenderPass`].
+    pub fn add_custom_pass(&mut self, pass: Box<dyn CustomRenderPass>) {
+        self.custom_passes.push(pass);
+    }
+
+    /// Queues `text` to be rasterized at `(x, y)`, in virtual pixels, using
+    /// the embedded `BitmapFont::debug_font`. Cleared after the next
+    /// [`Chroma::render`] call, so this is meant to be called once per
+    /// frame — an FPS counter is two lines:
+    /// `chroma.draw_debug_text(4, 4, &format!("FPS {fps}"))` then `render()`.
+    #[cfg(feature = "debug-font")]
+    pub fn draw_debug_text(&mut self, x: i32, y: i32, text: &str) {
+        self.debug_text
+            .push((cgmath::Vector2::new(x as f32, y as f32), text.to_string()));
+    }
+
+    /// Rolling frame-time statistics, updated automatically by
+    /// [`Chroma::render`].
+    pub fn fps_tracker(&self) -> &FpsTracker {
+        &self.fps_tracker
+    }
+
+    /// Longer-window frame-time percentiles and scene counters, updated
+    /// automatically by [`Chroma::render`]. See [`Chroma::fps_tracker`] for
+    /// a shorter-window rolling FPS average instead.
+    pub fn frame_stats(&self) -> &FrameStats {
+        &self.frame_stats
+    }
+
+    /// Draws [`Chroma::frame_stats`] as text via [`Chroma::draw_debug_text`].
+    /// A no-op when the `debug-font` feature is disabled, so callers don't
+    /// need to `cfg`-gate the call site themselves.
+    pub fn draw_debug_overlay(&mut self) {
+        #[cfg(feature = "debug-font")]
+        {
+            let stats = self.frame_stats();
+            let text = format!(
+                "{:.0} fps ({:.0} smoothed)\n{:.2}ms p50  {:.2}ms p99\n{} instances, {} uploads",
+                stats.instantaneous_fps(),
+                stats.smoothed_fps(),
+                stats.frame_time_percentile_ms(50.0),
+                stats.frame_time_percentile_ms(99.0),
+                stats.instance_count(),
+                stats.instance_buffer_uploads(),
+            );
+            self.draw_debug_text(4, 4, &text);
+        }
+    }
+
+    /// The queue of built-in engine events ([`ChromaEvent::TileAdded`],
+    /// [`ChromaEvent::WindowResized`], ...). Drain it once per frame to
+    /// react to them; game systems wanting their own event types should
+    /// hold their own [`EventQueue`] instead of extending this one.
+    pub fn events(&self) -> &EventQueue<ChromaEvent> {
+        &self.events
+    }
+
+    /// Records a frame boundary at `now` for [`Chroma::fps_tracker`]'s
+    /// rolling statistics. Called automatically at the end of
+    /// [`Chroma::render`]; only call this directly if frame timing needs to
+    /// be measured somewhere else in the loop.
+    pub fn update_fps(&mut self, now: std::time::Instant) {
+        self.fps_tracker.update(now);
+    }
+
+    /// Time elapsed since the previous [`Chroma::render`] call, updated
+    /// automatically at the start of `render`. Zero on the first frame
+    /// rather than a large or meaningless value.
+    ///
+    /// Uses [`instant::Instant`] internally rather than
+    /// `std::time::Instant`, which panics on wasm, so this works
+    /// everywhere chroma runs.
+    pub fn frame_delta(&self) -> std::time::Duration {
+        self.frame_delta
+    }
+
+    /// Time elapsed since this `Chroma` was constructed.
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.start_time.elapsed()
+    }
+
+    /// Draws the tracked rolling FPS at `(x, y)` (virtual pixels) via
+    /// [`Chroma::draw_debug_text`]. `font` is accepted for symmetry with the
+    /// CPU `Pixels::draw_text` path but is currently unused: debug text
+    /// always rasterizes with the embedded debug font until chroma grows a
+    /// general-purpose glyph-instance pipeline.
+    #[cfg(feature = "debug-font")]
+    pub fn draw_fps_counter(&mut self, x: u32, y: u32, _font: &pixels::BitmapFont) {
+        let fps = self.fps_tracker.fps();
+        self.draw_debug_text(x as i32, y as i32, &format!("FPS {fps:.0}"));
+    }
+
+    pub fn remove_tile(&mut self, handle: TileHandle) {
+        self.instances.remove(&handle.0);
+    }
+
+    pub fn move_tile(&mut self, handle: TileHandle, position: cgmath::Vector2<f32>) {
+        if let Some(instance) = self.instances.get_mut(&handle.0) {
+            instance.position = position;
+        }
+    }
+
+    /// Changes which sprite sheet index a tile displays without disturbing
+    /// its position or handle. Errors if `handle` doesn't refer to a live
+    /// tile.
+    #[must_use = "silently a no-op for a stale handle; check for ChromaError::InvalidTileHandle"]
+    pub fn set_tile_sprite(&mut self, handle: TileHandle, index: u32) -> Result<(), ChromaError> {
+        match self.instances.get_mut(&handle.0) {
+            Some(instance) => {
+                instance.index = index;
+                Ok(())
+            }
+            None => Err(ChromaError::InvalidTileHandle),
+        }
+    }
+
+    /// Encodes the current scene's instances as bincode bytes, suitable for
+    /// writing to a save file.
+    #[cfg(feature = "serde")]
+    pub fn serialize_instances(&self) -> Result<Vec<u8>, SerializeError> {
+        let instances: Vec<Instance> = self.instances.values().copied().collect();
+        Ok(bincode::serialize(&instances)?)
+    }
+
+    /// Replaces the current instance list with the instances encoded in
+    /// `data`, as previously produced by [`Chroma::serialize_instances`].
+    /// Existing [`TileHandle`]s are invalidated.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_instances(&mut self, data: &[u8]) -> Result<(), SerializeError> {
+        let instances: Vec<Instance> = bincode::deserialize(data)?;
+
+        self.instances.clear();
+        self.next_handle = 0;
+        for instance in instances {
+            self.instances.insert(self.next_handle, instance);
+            self.next_handle += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Uploads a decorative frame drawn full-window, behind the scaled game
+    /// image, in the region outside [`Chroma::clip_rect`] (and behind it,
+    /// though the game image fully covers it there). Call
+    /// [`Chroma::clear_border_image`] to restore the plain letterbox color.
+    pub fn set_border_image(&mut self, bytes: &[u8]) -> Result<(), ChromaError> {
+        let image = image::load_from_memory(bytes)?.to_rgba8();
+        let (width, height) = image.dimensions();
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("chroma-border-texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.surface_format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        self.queue.write_texture(
+            texture.as_image_copy(),
+            &image,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        self.renderer.set_border_texture(Some(texture));
+        Ok(())
+    }
+
+    /// Removes the border image previously set with [`Chroma::set_border_image`],
+    /// restoring the plain letterbox color.
+    pub fn clear_border_image(&mut self) {
+        self.renderer.set_border_texture(None);
+    }
+
+    /// Uploads `data`, a `width`x`height` RGBA8 image, as a new GPU texture
+    /// and returns a handle to it. Errors with
+    /// [`ChromaError::InvalidTextureData`] if `data` isn't exactly
+    /// `width * height * 4` bytes.
+    pub fn create_texture_from_rgba(
+        &mut self,
+        data: &[u8],
+        width: u32,
+        height: u32,
+        label: &str,
+    ) -> Result<TextureHandle, ChromaError> {
+        let expected = (width * height * 4) as usize;
+        if data.len() != expected {
+            return Err(ChromaError::InvalidTextureData {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.surface_format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        self.queue.write_texture(
+            texture.as_image_copy(),
+            data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let handle = TextureHandle(self.next_texture_handle);
+        self.next_texture_handle += 1;
+        self.textures.insert(
+            handle.0,
+            StoredTexture {
+                texture,
+                width,
+                height,
+            },
+        );
+        Ok(handle)
+    }
+
+    /// Selects `handle`, previously returned by
+    /// [`Chroma::create_texture_from_rgba`], as the texture sprite indices
+    /// are sampled from. Errors with [`ChromaError::InvalidTextureHandle`]
+    /// if the handle doesn't refer to a live texture.
+    pub fn set_active_sprite_sheet(&mut self, handle: TextureHandle) -> Result<(), ChromaError> {
+        if !self.textures.contains_key(&handle.0) {
+            return Err(ChromaError::InvalidTextureHandle);
+        }
+        self.active_sprite_sheet = Some(handle);
+        Ok(())
+    }
+
+    /// The texture set via [`Chroma::set_active_sprite_sheet`], if any.
+    pub fn active_sprite_sheet(&self) -> Option<TextureHandle> {
+        self.active_sprite_sheet
+    }
+
+    /// Uploads `data`, a `width`x`height` RGBA8 image, into the
+    /// `(x, y)`-`(x + width, y + height)` region of `handle`'s texture,
+    /// avoiding a full re-upload when only part of it changed (e.g.
+    /// streaming in newly-visible tilemap data as the camera scrolls).
+    /// Errors with [`ChromaError::InvalidTextureHandle`] if `handle`
+    /// doesn't refer to a live texture, or
+    /// [`ChromaError::InvalidTextureData`] if `data` isn't exactly
+    /// `width * height * 4` bytes.
+    pub fn update_texture_region(
+        &mut self,
+        handle: TextureHandle,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Result<(), ChromaError> {
+        let stored = self
+            .textures
+            .get(&handle.0)
+            .ok_or(ChromaError::InvalidTextureHandle)?;
+
+        let expected = (width * height * 4) as usize;
+        if data.len() != expected {
+            return Err(ChromaError::InvalidTextureData {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        self.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                ..stored.texture.as_image_copy()
+            },
+            data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Rebuilds `handle`'s texture with a full mipmap chain
+    /// (`floor(log2(max(w,h))) + 1` levels), for callers rendering a
+    /// zoomed-out sprite sheet who want bilinear-filtered mip sampling to
+    /// avoid aliasing. Errors with [`ChromaError::InvalidTextureHandle`] if
+    /// `handle` doesn't refer to a live texture.
+    ///
+    /// Mip level 0 is copied in from the existing texture for real. Levels
+    /// beyond that are allocated but left uninitialized: generating them
+    /// requires a downsampling compute or render pipeline, and this vendored
+    /// subset has no shader infrastructure yet (see [`CustomRenderPass`] for
+    /// the extension point a real downsampling pass would use once one
+    /// exists).
+    pub fn generate_mipmaps(&mut self, handle: TextureHandle) -> Result<(), ChromaError> {
+        let stored = self
+            .textures
+            .get(&handle.0)
+            .ok_or(ChromaError::InvalidTextureHandle)?;
+
+        let width = stored.width;
+        let height = stored.height;
+        let mip_level_count = texture::mip_level_count(width, height);
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let mipped = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.surface_format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_texture(stored.texture.as_image_copy(), mipped.as_image_copy(), size);
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        self.textures.insert(
+            handle.0,
+            StoredTexture {
+                texture: mipped,
+                width,
+                height,
+            },
+        );
+        Ok(())
+    }
+
+    /// Loads `bytes` (an encoded image, decoded the same way as
+    /// [`Chroma::set_border_image`]) as a GPU texture, returning the
+    /// existing handle instead of allocating a duplicate if this exact byte
+    /// sequence was already loaded through this method — useful for level
+    /// reloads that re-request the same sprite sheet. The cache key is a
+    /// `u64` hash of the raw input bytes; a collision would incorrectly
+    /// reuse an unrelated texture, but that's astronomically unlikely for
+    /// the handful of sprite sheets a game loads.
+    pub fn load_sprite_sheet_cached(&mut self, bytes: &[u8]) -> Result<TextureHandle, ChromaError> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some(&handle) = self.texture_cache.get(&key) {
+            if self.textures.contains_key(&handle.0) {
+                return Ok(handle);
+            }
+        }
+
+        let image = image::load_from_memory(bytes)?.to_rgba8();
+        let (width, height) = image.dimensions();
+        let handle =
+            self.create_texture_from_rgba(&image, width, height, "chroma-cached-sprite-sheet")?;
+        self.texture_cache.insert(key, handle);
+        Ok(handle)
+    }
+
+    /// Removes every entry from the cache [`Chroma::load_sprite_sheet_cached`]
+    /// uses, without freeing the GPU textures themselves — a later call with
+    /// the same bytes allocates a fresh texture. Use
+    /// [`Chroma::evict_texture`] to actually free one.
+    pub fn clear_texture_cache(&mut self) {
+        self.texture_cache.clear();
+    }
+
+    /// Frees the GPU texture behind `handle` and drops any cache entry
+    /// pointing to it, so a later [`Chroma::load_sprite_sheet_cached`] call
+    /// with the same bytes re-uploads rather than returning a stale handle.
+    /// A no-op if `handle` isn't a live texture.
+    pub fn evict_texture(&mut self, handle: TextureHandle) {
+        self.textures.remove(&handle.0);
+        self.texture_cache.retain(|_, &mut cached| cached != handle);
+        if self.active_sprite_sheet == Some(handle) {
+            self.active_sprite_sheet = None;
+        }
+    }
+
+    /// Loads a large tile grid for streaming, splitting it into
+    /// `chunk_size`x`chunk_size` chunks and returning a
+    /// [`StreamingTileMap`] the caller drives with
+    /// [`StreamingTileMap::update`] each tick to keep only the chunks near
+    /// the camera loaded as live tile instances. See [`StreamingTileMap`]
+    /// for `tmx`'s expected layout — this vendored engine has no Tiled
+    /// TMX/XML importer, only the minimal binary format documented there.
+    pub fn load_tiled_map_streaming(
+        &self,
+        tmx: &[u8],
+        chunk_size: u32,
+    ) -> Result<StreamingTileMap, ChromaError> {
+        StreamingTileMap::load(tmx, chunk_size)
+    }
+
+    /// Reads `path` with `tokio::fs::read`, then decodes and uploads it the
+    /// same way [`Chroma::load_sprite_sheet_cached`] does — so a game loop
+    /// loading sprite sheets from slow storage doesn't stall waiting on the
+    /// disk. Requires the `async-loading` feature; poll the returned future
+    /// from a `tokio` runtime.
+    #[cfg(all(feature = "async-loading", not(target_arch = "wasm32")))]
+    pub fn load_sprite_sheet_from_path_async(
+        &mut self,
+        path: impl Into<std::path::PathBuf>,
+    ) -> TextureLoadFuture<'_> {
+        let path = path.into();
+        Box::pin(async move {
+            let bytes = tokio::fs::read(path).await?;
+            self.load_sprite_sheet_cached(&bytes)
+        })
+    }
+
+    /// Fetches `url` with the browser's `fetch` API, then decodes and
+    /// uploads it the same way [`Chroma::load_sprite_sheet_cached`] does.
+    /// `wasm32` only, requires the `async-loading` feature; drive the
+    /// returned future with `wasm_bindgen_futures::spawn_local` or an
+    /// `.await` inside one.
+    #[cfg(all(feature = "async-loading", target_arch = "wasm32"))]
+    pub fn load_sprite_sheet_async(&mut self, url: &str) -> TextureLoadFuture<'_> {
+        let url = url.to_string();
+        Box::pin(async move {
+            let bytes = wasm_fetch::fetch_bytes(&url).await?;
+            self.load_sprite_sheet_cached(&bytes)
+        })
+    }
+
+    /// Draws a sprite for exactly one frame without allocating a
+    /// [`TileHandle`]. Useful for one-shot visual effects (explosions, hit
+    /// sparks) that don't need to be moved or removed later.
+    pub fn draw_sprite_once(&mut self, position: cgmath::Vector2<f32>, index: u32) {
+        self.ephemeral_instances.push(Instance::new(position, index));
+    }
+
+    /// Mutable access to a CPU-side background buffer at virtual resolution,
+    /// composited under every sprite instance in the same render pass — for
+    /// pixel art easier to plot directly (raycasters, particle fields, dirty
+    /// overlays) than to model as sprites. Laid out as RGBA8 rows with
+    /// `(0, 0)` at the top-left. Lazily allocated on first call; reallocated
+    /// (losing its contents) if the virtual resolution changes afterward.
+    pub fn frame_mut(&mut self) -> &mut [u8] {
+        self.renderer.frame_mut(self.virtual_size)
+    }
+
+    /// The rectangle, in physical window coordinates, that the scaled game
+    /// image occupies. Anything outside this rect is letterbox.
+    pub fn clip_rect(&self) -> (u32, u32, u32, u32) {
+        self.renderer.clip_rect()
+    }
+
+    /// The integer scale factor chosen to fit the virtual resolution into
+    /// the current window size.
+    pub fn scale_factor(&self) -> u32 {
+        self.renderer.scale_factor()
+    }
+
+    /// The width, in virtual pixels, of the resolution the game renders at.
+    pub fn virtual_width(&self) -> u32 {
+        self.virtual_size.0
+    }
+
+    /// The height, in virtual pixels, of the resolution the game renders at.
+    pub fn virtual_height(&self) -> u32 {
+        self.virtual_size.1
+    }
+
+    /// The `wgpu::TextureFormat` the window surface was configured with.
+    /// Useful for downstream code (a UI library, a custom render pass)
+    /// building compatible pipelines.
+    pub fn surface_format(&self) -> wgpu::TextureFormat {
+        self.surface_format
+    }
+
+    /// The format chroma's sprite pass renders in: always the same as
+    /// [`Chroma::surface_format`]. Sprites are drawn straight onto the
+    /// surface (see [`CustomRenderPass`]), so there is no separate
+    /// intermediate texture with a fixed format such as `Rgba8UnormSrgb`;
+    /// [`Chroma::render_to_vec`]'s off-screen texture also uses this format.
+    pub fn render_texture_format(&self) -> wgpu::TextureFormat {
+        self.surface_format
+    }
+
+    /// A rect covering the full virtual resolution, `(0, 0)` to
+    /// `(virtual_width, virtual_height)`. Useful for bounds-checking tile
+    /// placement.
+    pub fn virtual_rect(&self) -> Rect {
+        Rect {
+            x: 0,
+            y: 0,
+            width: self.virtual_size.0,
+            height: self.virtual_size.1,
+        }
+    }
+
+    /// The size, in physical window pixels, of the scaled game image (i.e.
+    /// the width/height components of [`Chroma::clip_rect`]).
+    pub fn letterbox_size(&self) -> (u32, u32) {
+        let (_, _, width, height) = self.clip_rect();
+        (width, height)
+    }
+
+    /// Maps a window-space position (e.g. from a `PhysicalPosition` mouse
+    /// event) to a virtual pixel coordinate. `Ok` if `pos` falls inside
+    /// [`Chroma::clip_rect`] and maps to a pixel within the virtual
+    /// resolution; otherwise `Err` with the coordinate clamped to the
+    /// virtual resolution's bounds.
+    pub fn window_pos_to_pixel(&self, pos: (f32, f32)) -> Result<(u32, u32), (i32, i32)> {
+        self.renderer.scaling_matrix().window_pos_to_pixel(pos, self.virtual_size)
+    }
+
+    /// The inverse of [`Chroma::window_pos_to_pixel`]: the window-space
+    /// position of a virtual pixel's top-left corner. Round-trips through
+    /// `window_pos_to_pixel` back to `(x, y)`.
+    pub fn pixel_pos_to_window(&self, x: u32, y: u32) -> (f32, f32) {
+        self.renderer.scaling_matrix().pixel_pos_to_window((x, y))
+    }
+
+    /// The on-screen size, in window pixels, of a single virtual pixel at
+    /// the current scale factor.
+    pub fn window_pixel_size(&self) -> (u32, u32) {
+        let scale = self.scale_factor();
+        (scale, scale)
+    }
+
+    pub fn window(&self) -> &winit::window::Window {
+        &self.window
+    }
+
+    /// Sets the window title. A thin forwarder to `window().set_title` so
+    /// callers don't need to know about the winit `Window` type just to
+    /// show a dynamic title (FPS, level name, etc.).
+    pub fn set_title(&self, title: &str) {
+        self.window.set_title(title);
+    }
+
+    /// Sets the window's icon. A thin forwarder to `window().set_window_icon`.
+    pub fn set_window_icon(&self, icon: winit::window::Icon) {
+        self.window.set_window_icon(Some(icon));
+    }
+
+    /// Reconfigures the surface for a new window size, e.g. from
+    /// `WindowEvent::Resized`. Accepts anything convertible to
+    /// [`SurfaceSize`] — a `winit::dpi::PhysicalSize<u32>` straight off the
+    /// event works via [`SurfaceSize`]'s `From` impl. A zero-area size (a
+    /// minimized window reports one) pauses rendering instead of touching
+    /// the surface — see [`Chroma::is_render_paused`] — and resumes,
+    /// reconfiguring the surface normally, on the next non-zero resize.
+    pub fn resize(&mut self, new_size: impl Into<SurfaceSize>) {
+        let new_size = new_size.into();
+        self.render_pause.on_resize(new_size.width, new_size.height);
+        if new_size.width == 0 || new_size.height == 0 {
+            return;
+        }
+
+        self.surface.configure(
+            &self.device,
+            &wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: self.surface_format,
+                width: new_size.width,
+                height: new_size.height,
+                present_mode: self.present_mode,
+                alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                view_formats: vec![],
+                desired_maximum_frame_latency: 2,
+            },
+        );
+
+        self.renderer
+            .resize(self.virtual_size, (new_size.width, new_size.height));
+
+        self.events
+            .push(ChromaEvent::WindowResized(new_size.width, new_size.height));
+    }
+
+    /// Marks the window as occluded (fully hidden behind other windows,
+    /// e.g. `WindowEvent::Occluded(true)`) or not. Like a zero-size
+    /// [`Chroma::resize`], this pauses [`Chroma::render`] until cleared —
+    /// see [`Chroma::is_render_paused`].
+    pub fn set_occluded(&mut self, occluded: bool) {
+        self.render_pause.on_occluded(occluded);
+    }
+
+    /// Whether [`Chroma::render`] is currently skipping GPU work because the
+    /// window is minimized or occluded. [`Chroma::run`] also uses this to
+    /// switch the event loop to `ControlFlow::Wait` instead of polling while
+    /// there's nothing to draw.
+    pub fn is_render_paused(&self) -> bool {
+        self.render_pause.is_paused()
+    }
+
+    /// Caps how often [`Chroma::render`] presents a frame, for
+    /// `PresentMode::Immediate`/`Mailbox` where an uncapped loop would spin
+    /// at thousands of FPS. `None` (the default) presents as fast as the
+    /// surface allows. A no-op on wasm, where the browser already paces
+    /// `requestAnimationFrame` callbacks.
+    ///
+    /// This only slows down *presentation*; it doesn't touch
+    /// [`GameLoop`]'s fixed-timestep accumulator, which measures real wall
+    /// clock time on every call and so already accounts for whatever extra
+    /// time `render` spends sleeping here — simulation speed doesn't drift.
+    pub fn set_frame_limit(&mut self, target_fps: Option<u32>) {
+        self.frame_limiter.set_limit(target_fps);
+    }
+
+    /// Reconfigures the window surface and rebuilds the scaling pipeline for
+    /// its current size. Call this after [`Chroma::render`] returns
+    /// [`ChromaError::Surface`]`(`[`wgpu::SurfaceError::Lost`]`)` to recover
+    /// before rendering the next frame.
+    pub fn recover_surface(&mut self) {
+        let size = self.window.inner_size();
+        self.resize(size);
+    }
+
+    /// Registers a callback invoked when the GPU device reports an
+    /// uncaptured error, including driver-level device loss (a crash, a
+    /// hot-unplug). Long-running games can use this to log the failure or
+    /// prompt the player before calling [`Chroma::try_recover_device`].
+    pub fn set_device_lost_handler(&self, f: impl Fn(wgpu::DeviceLostReason, &str) + 'static) {
+        self.device.on_uncaptured_error(Box::new(move |error| {
+            f(wgpu::DeviceLostReason::Unknown, &error.to_string());
+        }));
+    }
+
+    /// Attempts to recover from a lost GPU device by re-requesting an
+    /// adapter and device from scratch and reconfiguring the surface for
+    /// them. Sprite instances and camera/lighting state all live on the CPU
+    /// side and survive untouched; only the GPU handles [`Chroma::render`]
+    /// depends on are rebuilt here, with the border texture re-uploaded the
+    /// next time [`Chroma::set_border_image`] runs.
+    pub fn try_recover_device(&mut self) -> Result<(), ChromaError> {
+        let instance = wgpu::Instance::default();
+        let surface = instance.create_surface(&self.window)?;
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            compatible_surface: Some(&surface),
+            ..Default::default()
+        }))
+        .ok_or(ChromaError::AdapterNotFound)?;
+
+        let (device, queue) = pollster::block_on(
+            adapter.request_device(&wgpu::DeviceDescriptor::default(), None),
+        )?;
+
+        self.surface = surface;
+        self.device = device;
+        self.queue = queue;
+        self.recover_surface();
+
+        Ok(())
+    }
+
+    /// Uploads the current frame's instances, runs the sprite pass and every
+    /// pass added with [`Chroma::add_custom_pass`], and presents the window
+    /// surface. The sprite pass records no draw commands of its own, so the
+    /// instances are not drawn; only custom passes write into the surface.
+    ///
+    /// A minimized window or a display/DPI change can invalidate the
+    /// surface mid-run, in which case this returns
+    /// [`ChromaError::Surface`]`(`[`wgpu::SurfaceError::Lost`]`)`. The
+    /// standard recovery is to reconfigure the surface and try again next
+    /// frame:
+    ///
+    /// ```ignore
+    /// if let Err(ChromaError::Surface(wgpu::SurfaceError::Lost)) = chroma.render() {
+    ///     chroma.recover_surface();
+    /// }
+    /// ```
+    ///
+    /// [`wgpu::SurfaceError::OutOfMemory`] means the GPU itself is out of
+    /// memory; it isn't recoverable by reconfiguring the surface, so
+    /// callers should treat it as fatal and exit rather than retrying.
+    #[must_use = "surface errors must be handled; call recover_surface() on ChromaError::Surface(wgpu::SurfaceError::Lost)"]
+    pub fn render(&mut self) -> Result<(), ChromaError> {
+        if self.render_pause.is_paused() {
+            return Ok(());
+        }
+
+        let now = instant::Instant::now();
+        self.frame_delta = now.duration_since(self.last_frame_time);
+        self.last_frame_time = now;
+
+        let raw_instances: Vec<InstanceRaw> = self
+            .instances
+            .values()
+            .copied()
+            .filter(|instance| instance.visible)
+            .chain(self.ephemeral_instances.iter().copied())
+            .map(InstanceRaw::from)
+            .collect();
+        self.ephemeral_instances.clear();
+        self.renderer.configure_instances(&self.device, &self.queue, &raw_instances);
+        self.frame_stats.record_frame(
+            self.frame_delta,
+            self.renderer.instance_count(),
+            self.renderer.instance_buffer_uploads(),
+        );
+
+        #[cfg(feature = "debug-font")]
+        self.renderer.set_debug_text(std::mem::take(&mut self.debug_text));
+
+        let surface_texture = self.surface.get_current_texture()?;
+        let view = surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("chroma-encoder"),
+            });
+
+        self.renderer.render(&mut encoder, &view);
+
+        for pass in &self.custom_passes {
+            pass.encode(&self.device, &self.queue, &mut encoder, &view, &view);
+        }
+
+        self.queue.submit(std::iter::once(encoder.finish()));
+        surface_texture.present();
+
+        self.frame_counter += 1;
+        self.update_fps(std::time::Instant::now());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let remaining = self.frame_limiter.remaining(self.last_present_time.elapsed());
+            if !remaining.is_zero() {
+                hybrid_sleep(remaining);
+            }
+        }
+        self.last_present_time = instant::Instant::now();
+
+        Ok(())
+    }
+
+    /// Renders the current frame's instances into an off-screen intermediate
+    /// texture, at [`Chroma::render_texture_format`], instead of the window
+    /// surface, and reads it back as tightly packed RGBA8 bytes — for
+    /// golden-file snapshot tests and property-based rendering tests that
+    /// need to inspect output without a display. Works headless: no surface
+    /// presentation is involved.
+    ///
+    /// Behind the `test-utils` feature so the readback buffer this needs
+    /// doesn't ship in production builds. Blocks the calling thread on the
+    /// buffer map, same as [`Pixels::read_texture`](crate::pixels::Pixels::read_texture).
+    ///
+    /// The instanced sprite draw call itself is stubbed out in this
+    /// vendored subset (see [`Chroma::render`]'s implementation), so the
+    /// returned bytes are currently just the pass's clear color rather than
+    /// composited sprites; this becomes a real snapshot once that pipeline
+    /// is built, with no change to the signature.
+    #[cfg(feature = "test-utils")]
+    pub fn render_to_vec(&mut self) -> Result<Vec<u8>, ChromaError> {
+        self.render_offscreen_rgba()
+    }
+
+    /// Renders the current frame's instances off-screen and encodes the
+    /// result as a PNG at `path`, using the `image` crate already pulled in
+    /// for texture loading. Not available on wasm, where there's no
+    /// filesystem to write to.
+    ///
+    /// Shares [`Chroma::render_to_vec`]'s readback path but isn't gated
+    /// behind the `test-utils` feature — screenshots are a normal runtime
+    /// feature, not a testing-only one.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_screenshot(&mut self, path: impl AsRef<std::path::Path>) -> Result<(), ChromaError> {
+        let (width, height) = self.virtual_size;
+        let pixels = self.render_offscreen_rgba()?;
+
+        use image::ImageEncoder;
+
+        let mut png_bytes = Vec::new();
+        image::codecs::png::PngEncoder::new(&mut png_bytes)
+            .write_image(&pixels, width, height, image::ColorType::Rgba8)?;
+        std::fs::write(path, png_bytes)?;
+
+        Ok(())
+    }
+
+    /// The shared implementation behind [`Chroma::render_to_vec`] and
+    /// [`Chroma::save_screenshot`]: renders the current instances into a
+    /// fresh off-screen texture and reads it back as tightly packed RGBA8
+    /// bytes.
+    #[cfg(any(feature = "test-utils", not(target_arch = "wasm32")))]
+    fn render_offscreen_rgba(&mut self) -> Result<Vec<u8>, ChromaError> {
+        let (width, height) = self.virtual_size;
+        let format = self.render_texture_format();
+
+        let raw_instances: Vec<InstanceRaw> = self
+            .instances
+            .values()
+            .copied()
+            .filter(|instance| instance.visible)
+            .chain(self.ephemeral_instances.iter().copied())
+            .map(InstanceRaw::from)
+            .collect();
+        self.ephemeral_instances.clear();
+        self.renderer.configure_instances(&self.device, &self.queue, &raw_instances);
+
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("chroma-render-to-vec-texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("chroma-render-to-vec-encoder"),
+            });
+        self.renderer.render(&mut encoder, &view);
+
+        let bytes_per_row = width * 4;
+        let padded_bytes_per_row =
+            bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("chroma-render-to-vec-buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver
+            .recv()
+            .map_err(|_| ChromaError::BufferMap("map_async callback was dropped".to_string()))?
+            .map_err(|err| ChromaError::BufferMap(err.to_string()))?;
+
+        let padded_data = slice.get_mapped_range();
+        let mut tightly_packed = Vec::with_capacity((bytes_per_row * height) as usize);
+        for row in padded_data.chunks(padded_bytes_per_row as usize) {
+            tightly_packed.extend_from_slice(&row[..bytes_per_row as usize]);
+        }
+        drop(padded_data);
+        buffer.unmap();
+
+        // `format` follows the surface, which may store BGRA; swap to RGBA.
+        if matches!(format, wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb) {
+            for pixel in tightly_packed.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Ok(tightly_packed)
+    }
+
+    /// Owns a winit event loop end to end: forwards resize events, feeds an
+    /// [`Input`] from keyboard and mouse events (translate the cursor to
+    /// pixel coordinates with [`Input::cursor_pixel_position`]), steps a
+    /// fixed-timestep [`GameLoop`] at `target_fps` (calling `update` with
+    /// that frame's [`Input`] and the fixed `dt`, then clearing `Input`'s
+    /// edge-triggered state), then
+    /// calls `draw` and [`Chroma::render`] once per redraw, recovering
+    /// automatically from
+    /// [`ChromaError::Surface`]`(`[`wgpu::SurfaceError::Lost`]`)`. Exits on
+    /// `WindowEvent::CloseRequested`. While the window is minimized or
+    /// occluded (see [`Chroma::is_render_paused`]), `draw`/`render` are
+    /// skipped for each redraw and the loop switches from
+    /// `ControlFlow::Poll` to `ControlFlow::Wait` so it isn't burning CPU
+    /// polling a window with nothing to show.
+    ///
+    /// Entirely optional — `Chroma` works fine driven by a hand-rolled event
+    /// loop instead; this just saves writing the same boilerplate every
+    /// project ends up with. On wasm, `event_loop.run` hands control to the
+    /// browser and never returns to its caller; on native it blocks until
+    /// the window closes.
+    pub fn run(
+        mut self,
+        event_loop: winit::event_loop::EventLoop<()>,
+        target_fps: u32,
+        mut update: impl FnMut(&mut Chroma, &Input, f32) + 'static,
+        mut draw: impl FnMut(&mut Chroma) + 'static,
+    ) -> ! {
+        use winit::event::{ElementState, Event, WindowEvent};
+        use winit::keyboard::PhysicalKey;
+
+        let mut game_loop = GameLoop::new(target_fps);
+        // Tracks every winit event (including repeats, scroll, and the
+        // cursor leaving the window) for `update`'s `&Input` argument. Kept
+        // separate from `self.input` because `update` needs `&mut Chroma`
+        // and `&Input` at once, which `self.input` can't provide; `self.input`
+        // is still kept current alongside it via `process_keyboard_event`/
+        // `process_mouse_event` below, for callers that only have a `&Chroma`.
+        let mut input = Input::new();
+        let window_id = self.window.id();
+
+        event_loop.run(move |event, _, control_flow| {
+            match event {
+                Event::WindowEvent { event, window_id: id } if id == window_id => {
+                    input.handle_event(&event);
+                    match event {
+                        WindowEvent::CloseRequested => control_flow.set_exit(),
+                        WindowEvent::Resized(size) => self.resize(size),
+                        WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+                            self.resize(*new_inner_size)
+                        }
+                        WindowEvent::Occluded(occluded) => self.set_occluded(occluded),
+                        WindowEvent::KeyboardInput { event: key_event, .. } => {
+                            if !key_event.repeat {
+                                if let PhysicalKey::Code(key) = key_event.physical_key {
+                                    self.process_keyboard_event(input::KeyboardEvent {
+                                        key,
+                                        pressed: key_event.state == ElementState::Pressed,
+                                    });
+                                }
+                            }
+                        }
+                        WindowEvent::CursorMoved { position, .. } => {
+                            self.process_mouse_event(input::MouseEvent::Moved {
+                                position: (position.x as f32, position.y as f32),
+                            });
+                        }
+                        WindowEvent::MouseInput { state, button, .. } => {
+                            self.process_mouse_event(input::MouseEvent::Button {
+                                button,
+                                pressed: state == ElementState::Pressed,
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                Event::MainEventsCleared => {
+                    game_loop.update(std::time::Instant::now(), |dt| update(&mut self, &input, dt));
+                    input.end_frame();
+                    self.input.end_frame();
+                    self.window.request_redraw();
+                }
+                Event::RedrawRequested(id) if id == window_id => {
+                    if !self.is_render_paused() {
+                        draw(&mut self);
+                        if let Err(ChromaError::Surface(wgpu::SurfaceError::Lost)) = self.render() {
+                            self.recover_surface();
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if self.is_render_paused() {
+                control_flow.set_wait();
+            } else {
+                control_flow.set_poll();
+            }
+        })
+    }
+}
+
+/// Sleeps for `duration`, coarsely via `thread::sleep` for most of it, then
+/// spins for the last millisecond so [`Chroma::set_frame_limit`] hits its
+/// target without over-sleeping by an OS scheduler quantum.
+#[cfg(not(target_arch = "wasm32"))]
+fn hybrid_sleep(duration: std::time::Duration) {
+    const SPIN_MARGIN: std::time::Duration = std::time::Duration::from_millis(1);
+
+    let deadline = instant::Instant::now() + duration;
+    if duration > SPIN_MARGIN {
+        std::thread::sleep(duration - SPIN_MARGIN);
+    }
+    while instant::Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
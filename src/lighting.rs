@@ -0,0 +1,54 @@
+/// A single point light registered with [`crate::Chroma::add_light`].
+/// Lights move with the camera, the same as tiles.
+#[derive(Debug, Clone, Copy)]
+pub struct Light {
+    pub position: cgmath::Vector2<f32>,
+    pub radius: f32,
+    pub color: [f32; 3],
+}
+
+/// A handle to a light previously added with [`crate::Chroma::add_light`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LightHandle(pub(crate) u64);
+
+/// The lighting state: an ambient level plus a set of additive point
+/// lights. Only the light data is stored; no light texture is rendered and
+/// nothing is multiplied over the sprite output.
+pub(crate) struct LightingPass {
+    lights: std::collections::HashMap<u64, Light>,
+    next_handle: u64,
+    ambient: f32,
+}
+
+impl LightingPass {
+    pub(crate) fn new() -> Self {
+        Self {
+            lights: std::collections::HashMap::new(),
+            next_handle: 0,
+            ambient: 0.1,
+        }
+    }
+
+    pub(crate) fn add_light(&mut self, light: Light) -> LightHandle {
+        let handle = self.next_handle;
+        self.next_handle += 1;
+        self.lights.insert(handle, light);
+        LightHandle(handle)
+    }
+
+    pub(crate) fn remove_light(&mut self, handle: LightHandle) {
+        self.lights.remove(&handle.0);
+    }
+
+    pub(crate) fn set_ambient(&mut self, ambient: f32) {
+        self.ambient = ambient.clamp(0.0, 1.0);
+    }
+
+    pub(crate) fn ambient(&self) -> f32 {
+        self.ambient
+    }
+
+    pub(crate) fn lights(&self) -> impl Iterator<Item = &Light> {
+        self.lights.values()
+    }
+}
@@ -0,0 +1,95 @@
+//! Grid-based A* pathfinding over a [`CollisionGrid`]'s walkable cells.
+//! Only available with the `pathfinding` feature enabled.
+
+use crate::CollisionGrid;
+
+/// Finds paths across a [`CollisionGrid`], treating any cell carrying
+/// [`crate::CollisionFlags::SOLID`] as impassable and every other cell as
+/// walkable with a uniform cost of `1`.
+#[derive(Debug)]
+pub struct PathGrid<'grid> {
+    grid: &'grid CollisionGrid,
+}
+
+impl<'grid> PathGrid<'grid> {
+    pub fn new(grid: &'grid CollisionGrid) -> Self {
+        Self { grid }
+    }
+
+    fn neighbors(&self, (x, y): (u32, u32)) -> Vec<((u32, u32), u32)> {
+        [(1, 0), (-1, 0), (0, 1), (0, -1)]
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                (nx < self.grid.width && ny < self.grid.height && !self.grid.is_solid(nx, ny))
+                    .then_some(((nx, ny), 1))
+            })
+            .collect()
+    }
+
+    /// Finds a shortest walkable path from `from` to `to` with A*, using a
+    /// Manhattan-distance heuristic. Returns tile coordinates the caller
+    /// can convert to virtual pixel positions with the tile size passed to
+    /// [`crate::Chroma::set_tile_size`]. `None` if no path exists.
+    pub fn find_path(&self, from: (u32, u32), to: (u32, u32)) -> Option<Vec<(u32, u32)>> {
+        self.find_path_with_heuristic(from, to, manhattan_distance)
+    }
+
+    /// Like [`PathGrid::find_path`], but with a caller-supplied heuristic
+    /// instead of the default Manhattan distance. The heuristic must never
+    /// overestimate the true remaining cost for A* to guarantee a shortest
+    /// path.
+    pub fn find_path_with_heuristic(
+        &self,
+        from: (u32, u32),
+        to: (u32, u32),
+        heuristic: impl Fn((u32, u32), (u32, u32)) -> u32,
+    ) -> Option<Vec<(u32, u32)>> {
+        pathfinding::directed::astar::astar(
+            &from,
+            |&pos| self.neighbors(pos),
+            |&pos| heuristic(pos, to),
+            |&pos| pos == to,
+        )
+        .map(|(path, _cost)| path)
+    }
+}
+
+fn manhattan_distance(a: (u32, u32), b: (u32, u32)) -> u32 {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CollisionFlags;
+
+    #[test]
+    fn finds_straight_path_on_open_grid() {
+        let grid = CollisionGrid::new(4, 1);
+        let path_grid = PathGrid::new(&grid);
+        assert_eq!(
+            path_grid.find_path((0, 0), (3, 0)),
+            Some(vec![(0, 0), (1, 0), (2, 0), (3, 0)])
+        );
+    }
+
+    #[test]
+    fn routes_around_a_solid_wall() {
+        let mut grid = CollisionGrid::new(3, 3);
+        grid.set(1, 0, CollisionFlags::SOLID);
+        grid.set(1, 1, CollisionFlags::SOLID);
+        let path_grid = PathGrid::new(&grid);
+        let path = path_grid.find_path((0, 0), (2, 0)).unwrap();
+        assert!(path.contains(&(1, 2)));
+    }
+
+    #[test]
+    fn returns_none_when_fully_walled_off() {
+        let mut grid = CollisionGrid::new(3, 1);
+        grid.set(1, 0, CollisionFlags::SOLID);
+        let path_grid = PathGrid::new(&grid);
+        assert_eq!(path_grid.find_path((0, 0), (2, 0)), None);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+/// A fixed-width bitmap font: a glyph sheet image plus a code-point-to-cell
+/// mapping. Only the sheet's alpha channel is used — RGB is discarded in
+/// favor of whatever color [`FrameBuffer::draw_text`](super::frame_buffer::FrameBuffer::draw_text)
+/// is asked to draw with. Characters missing from the mapping render
+/// `fallback_glyph`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitmapFont {
+    sheet: Vec<u8>,
+    sheet_width: u32,
+    glyph_width: u32,
+    glyph_height: u32,
+    glyphs: HashMap<char, (u32, u32)>,
+    fallback_glyph: (u32, u32),
+}
+
+impl BitmapFont {
+    /// `sheet` is an RGBA8 image `sheet_width` wide, laid out as a grid of
+    /// `glyph_width` by `glyph_height` cells. `glyphs` maps characters to
+    /// their `(column, row)` cell; characters missing from the map render
+    /// `fallback_glyph`.
+    pub fn new(
+        sheet: Vec<u8>,
+        sheet_width: u32,
+        glyph_width: u32,
+        glyph_height: u32,
+        glyphs: HashMap<char, (u32, u32)>,
+        fallback_glyph: (u32, u32),
+    ) -> Self {
+        Self {
+            sheet,
+            sheet_width,
+            glyph_width,
+            glyph_height,
+            glyphs,
+            fallback_glyph,
+        }
+    }
+
+    pub(crate) fn glyph_width(&self) -> u32 {
+        self.glyph_width
+    }
+
+    pub(crate) fn glyph_height(&self) -> u32 {
+        self.glyph_height
+    }
+
+    pub(crate) fn cell_for(&self, c: char) -> (u32, u32) {
+        self.glyphs.get(&c).copied().unwrap_or(self.fallback_glyph)
+    }
+
+    /// The alpha value at `(local_x, local_y)` within `cell`'s glyph, or `0`
+    /// (transparent) if that cell falls outside `sheet` — e.g. a `glyphs`
+    /// map or `fallback_glyph` pointing past the sheet's edge — matching
+    /// [`super::frame_buffer::FrameBuffer`]'s convention of clipping bad
+    /// geometry rather than panicking on it.
+    pub(crate) fn alpha_at(&self, cell: (u32, u32), local_x: u32, local_y: u32) -> u8 {
+        let (col, row) = cell;
+        // Widen to `u64` before multiplying: an out-of-bounds `cell` (e.g. a
+        // `fallback_glyph` of `(u32::MAX, u32::MAX)`) would otherwise
+        // overflow this arithmetic itself, panicking before the `.get()`
+        // bounds check below ever gets a chance to clip it.
+        let sheet_x = col as u64 * self.glyph_width as u64 + local_x as u64;
+        let sheet_y = row as u64 * self.glyph_height as u64 + local_y as u64;
+        let offset = (sheet_y * self.sheet_width as u64 + sheet_x) * 4 + 3;
+        usize::try_from(offset).ok().and_then(|offset| self.sheet.get(offset)).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn font() -> BitmapFont {
+        // 2x1 glyph grid, 2x2 pixels each: sheet is 4 wide, 2 tall, RGBA8.
+        // Glyph (0, 0)'s alpha channel is 10/20/30/40; (1, 0)'s is 50/60/70/80.
+        let mut sheet = vec![0u8; 4 * 4 * 2];
+        let mut set_alpha = |x: u32, y: u32, a: u8| {
+            sheet[((y * 4 + x) * 4 + 3) as usize] = a;
+        };
+        set_alpha(0, 0, 10);
+        set_alpha(1, 0, 20);
+        set_alpha(0, 1, 30);
+        set_alpha(1, 1, 40);
+        set_alpha(2, 0, 50);
+        set_alpha(3, 0, 60);
+        set_alpha(2, 1, 70);
+        set_alpha(3, 1, 80);
+        BitmapFont::new(sheet, 4, 2, 2, HashMap::new(), (0, 0))
+    }
+
+    #[test]
+    fn alpha_at_reads_the_requested_cell_and_offset() {
+        let font = font();
+        assert_eq!(font.alpha_at((0, 0), 1, 0), 20);
+        assert_eq!(font.alpha_at((1, 0), 0, 1), 70);
+    }
+
+    #[test]
+    fn alpha_at_returns_transparent_for_an_out_of_bounds_cell() {
+        let font = font();
+        assert_eq!(font.alpha_at((u32::MAX, u32::MAX), 0, 0), 0);
+        assert_eq!(font.alpha_at((5, 5), 0, 0), 0);
+    }
+}
+
+#[cfg(feature = "debug-font")]
+impl BitmapFont {
+    /// A tiny embedded 8x8 bitmap font covering digits, uppercase letters,
+    /// and basic punctuation — enough for an FPS counter or a debug readout
+    /// without shipping a font image. Behind the `debug-font` feature so
+    /// binary-size-sensitive users can opt out.
+    pub fn debug_font() -> Self {
+        super::debug_font_data::build()
+    }
+}
@@ -0,0 +1,400 @@
+use super::renderers::ScalingRenderer;
+use super::{Pixels, PixelsContext, SurfaceSize, SurfaceTexture};
+
+/// Builds a [`Pixels`] instance, mirroring the upstream `pixels` crate's
+/// builder so the vendored copy stays a drop-in replacement.
+///
+/// `surface_texture` has no sensible default, so it's `None` until
+/// [`PixelsBuilder::surface_texture`] sets it (or [`PixelsBuilder::new`]
+/// takes it upfront); either way, [`PixelsBuilder::build`] errors with
+/// [`crate::ChromaError::MissingSurfaceTexture`] if it's still unset.
+pub struct PixelsBuilder<'win, 'dev> {
+    surface_texture: Option<SurfaceTexture<'win>>,
+    pixel_size: (u32, u32),
+    texture_format: Option<wgpu::TextureFormat>,
+    msaa_samples: u32,
+    present_mode: wgpu::PresentMode,
+    clear_color: wgpu::Color,
+    blend_state: wgpu::BlendState,
+    backends: wgpu::Backends,
+    power_preference: wgpu::PowerPreference,
+    force_fallback_adapter: bool,
+    device_descriptor: Option<wgpu::DeviceDescriptor<'dev>>,
+}
+
+/// `surface_texture` is reported through its own `Debug` impl (which
+/// already hides the non-`Debug` `wgpu::Surface` inside it);
+/// `device_descriptor` is reported as present/absent rather than expanded,
+/// since its contents aren't interesting for a builder still being configured.
+impl std::fmt::Debug for PixelsBuilder<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PixelsBuilder")
+            .field("surface_texture", &self.surface_texture)
+            .field("pixel_size", &self.pixel_size)
+            .field("texture_format", &self.texture_format)
+            .field("msaa_samples", &self.msaa_samples)
+            .field("present_mode", &self.present_mode)
+            .field("clear_color", &self.clear_color)
+            .field("blend_state", &self.blend_state)
+            .field("backends", &self.backends)
+            .field("power_preference", &self.power_preference)
+            .field("force_fallback_adapter", &self.force_fallback_adapter)
+            .field("device_descriptor", &self.device_descriptor.is_some())
+            .finish()
+    }
+}
+
+/// A `128`x`112` pixel buffer with no surface texture yet. Holds the
+/// defaults [`PixelsBuilder::new`] starts from: `new` fills in the size and
+/// surface texture and takes everything else from here, since a real
+/// `SurfaceTexture` can't be defaulted.
+impl Default for PixelsBuilder<'_, '_> {
+    fn default() -> Self {
+        Self {
+            surface_texture: None,
+            pixel_size: (128, 112),
+            texture_format: None,
+            msaa_samples: 1,
+            present_mode: wgpu::PresentMode::Fifo,
+            clear_color: wgpu::Color::BLACK,
+            blend_state: wgpu::BlendState::REPLACE,
+            backends: wgpu::Backends::all(),
+            power_preference: wgpu::PowerPreference::default(),
+            force_fallback_adapter: false,
+            device_descriptor: None,
+        }
+    }
+}
+
+impl<'win, 'dev> PixelsBuilder<'win, 'dev> {
+    /// Starts building a `width`x`height` pixel buffer over `surface_texture`.
+    ///
+    /// ```ignore
+    /// let instance = wgpu::Instance::default();
+    /// let surface_texture = SurfaceTexture::from_window(&instance, &window, size.width, size.height)?;
+    /// let pixels = PixelsBuilder::new(320, 240, surface_texture).build()?;
+    /// ```
+    ///
+    /// Equivalent to `PixelsBuilder::default().width(width).height(height).surface_texture(surface_texture)`
+    /// — use that path instead if `surface_texture` isn't known yet when
+    /// the builder is started.
+    pub fn new(width: u32, height: u32, surface_texture: SurfaceTexture<'win>) -> Self {
+        Self {
+            surface_texture: Some(surface_texture),
+            pixel_size: (width, height),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the pixel buffer's width. `128` by default.
+    pub fn width(mut self, width: u32) -> Self {
+        self.pixel_size.0 = width;
+        self
+    }
+
+    /// Sets the pixel buffer's height. `112` by default.
+    pub fn height(mut self, height: u32) -> Self {
+        self.pixel_size.1 = height;
+        self
+    }
+
+    /// Sets the surface to present the pixel buffer into. Required —
+    /// [`PixelsBuilder::build`] errors with
+    /// [`crate::ChromaError::MissingSurfaceTexture`] if it's still unset.
+    pub fn surface_texture(mut self, surface_texture: SurfaceTexture<'win>) -> Self {
+        self.surface_texture = Some(surface_texture);
+        self
+    }
+
+    /// Sets the adapter-selection options `build` requests with. The
+    /// `compatible_surface` field is ignored — `build` always requests
+    /// compatibility with its own surface — so only `power_preference` and
+    /// `force_fallback_adapter` are honored.
+    pub fn request_adapter_options(mut self, options: wgpu::RequestAdapterOptions<'_>) -> Self {
+        self.power_preference = options.power_preference;
+        self.force_fallback_adapter = options.force_fallback_adapter;
+        self
+    }
+
+    /// Sets just the adapter's power preference. A convenience over
+    /// [`PixelsBuilder::request_adapter_options`] for the common case.
+    pub fn power_preference(mut self, preference: wgpu::PowerPreference) -> Self {
+        self.power_preference = preference;
+        self
+    }
+
+    /// Sets the descriptor `build` requests the device with, unlocking
+    /// specific `wgpu::Features`/`wgpu::Limits` a custom render pass might
+    /// need. Defaults to `wgpu::DeviceDescriptor::default()` if unset.
+    pub fn device_descriptor(mut self, descriptor: wgpu::DeviceDescriptor<'dev>) -> Self {
+        self.device_descriptor = Some(descriptor);
+        self
+    }
+
+    /// Requests a specific texture format for the pixel texture, the
+    /// surface, and the render pass drawn between them, instead of letting
+    /// `build` auto-detect one (preferring an sRGB format, falling back to
+    /// whatever the surface offers first). `build` errors with
+    /// [`crate::ChromaError::UnsupportedTextureFormat`] if the surface
+    /// doesn't support the requested format.
+    pub fn texture_format(mut self, format: wgpu::TextureFormat) -> Self {
+        self.texture_format = Some(format);
+        self
+    }
+
+    /// Sets the surface's present mode. `Fifo` (vsync) by default.
+    pub fn present_mode(mut self, mode: wgpu::PresentMode) -> Self {
+        self.present_mode = mode;
+        self
+    }
+
+    /// Sets the color the surface is cleared to before the pixel buffer and
+    /// any custom render pass are drawn. Opaque black by default.
+    pub fn clear_color(mut self, color: wgpu::Color) -> Self {
+        self.clear_color = color;
+        self
+    }
+
+    /// Sets the blend state for a custom render pass drawn alongside the
+    /// upscaled pixel buffer (the pixel buffer itself is always uploaded
+    /// opaque). `BlendState::REPLACE` by default.
+    pub fn blend_state(mut self, state: wgpu::BlendState) -> Self {
+        self.blend_state = state;
+        self
+    }
+
+    /// Restricts which `wgpu::Backends` the adapter is requested from. All
+    /// backends by default.
+    pub fn wgpu_backend(mut self, backends: wgpu::Backends) -> Self {
+        self.backends = backends;
+        self
+    }
+
+    /// Enables multisample anti-aliasing for a custom render pass drawn
+    /// alongside the upscaled pixel buffer (3D elements, custom meshes) —
+    /// the pixel buffer itself is uploaded as-is and never benefits from
+    /// MSAA. `samples` must be `1`, `2`, `4`, or `8`.
+    pub fn with_msaa(mut self, samples: u32) -> Self {
+        assert!(
+            matches!(samples, 1 | 2 | 4 | 8),
+            "MSAA sample count must be 1, 2, 4, or 8, got {samples}"
+        );
+        self.msaa_samples = samples;
+        self
+    }
+
+    /// Alias for [`PixelsBuilder::clear_color`], for callers used to the
+    /// `with_`-prefixed naming [`PixelsBuilder::with_msaa`] already uses.
+    pub fn with_clear_color(self, color: wgpu::Color) -> Self {
+        self.clear_color(color)
+    }
+
+    /// Alias for [`PixelsBuilder::blend_state`].
+    pub fn with_blend_state(self, state: wgpu::BlendState) -> Self {
+        self.blend_state(state)
+    }
+
+    /// Alias for [`PixelsBuilder::texture_format`], naming the format of
+    /// the intermediate pixel texture the pixel buffer is drawn into.
+    /// Chroma's scaling pass currently upscales that texture directly onto
+    /// the surface with no format-converting blit step, so this and
+    /// [`PixelsBuilder::with_surface_texture_format`] both set the same
+    /// field today; they're kept as distinct methods so builder code
+    /// written against them separately still compiles once a real
+    /// conversion pass exists.
+    pub fn with_render_texture_format(self, format: wgpu::TextureFormat) -> Self {
+        self.texture_format(format)
+    }
+
+    /// Alias for [`PixelsBuilder::texture_format`]. See
+    /// [`PixelsBuilder::with_render_texture_format`] for why this and that
+    /// method are currently equivalent.
+    pub fn with_surface_texture_format(self, format: wgpu::TextureFormat) -> Self {
+        self.texture_format(format)
+    }
+
+    /// Alias for [`PixelsBuilder::present_mode`]: sets the surface's
+    /// present mode. `Fifo` waits for vsync (no tearing, capped at the
+    /// display's refresh rate); `Immediate` presents as soon as a frame is
+    /// ready (lowest latency, can tear); `Mailbox` presents the latest
+    /// ready frame at vsync without blocking the render loop on a full
+    /// queue (low latency, no tearing, not supported everywhere). `Fifo`
+    /// by default.
+    pub fn with_present_mode(self, mode: wgpu::PresentMode) -> Self {
+        self.present_mode(mode)
+    }
+
+    /// Alias for [`PixelsBuilder::wgpu_backend`]: restricts which
+    /// `wgpu::Backends` the adapter is requested from (Vulkan, Metal,
+    /// DX12, GL, ...). All backends by default, letting wgpu pick whatever
+    /// the platform supports best.
+    pub fn with_backend(self, backend: wgpu::Backends) -> Self {
+        self.wgpu_backend(backend)
+    }
+
+    /// Builds the [`Pixels`] instance natively, blocking the calling thread
+    /// on the adapter/device requests. A thin `pollster::block_on` wrapper
+    /// around [`PixelsBuilder::build_async`] — unavailable on `wasm32`,
+    /// where blocking isn't allowed; use `build_async` there instead, e.g.
+    /// driven from `wasm_bindgen_futures::spawn_local`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn build(self) -> Result<Pixels, crate::ChromaError> {
+        pollster::block_on(self.build_async())
+    }
+
+    /// Builds the [`Pixels`] instance, `await`ing the adapter/device
+    /// requests instead of blocking. The only build path available on
+    /// `wasm32`; native callers can use the blocking [`PixelsBuilder::build`]
+    /// instead.
+    pub async fn build_async(self) -> Result<Pixels, crate::ChromaError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: self.backends,
+            ..Default::default()
+        });
+        let surface_texture = self.surface_texture.ok_or(crate::ChromaError::MissingSurfaceTexture)?;
+        let SurfaceTexture { surface, size } = surface_texture;
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: self.power_preference,
+                force_fallback_adapter: self.force_fallback_adapter,
+                compatible_surface: Some(&surface),
+            })
+            .await
+            .ok_or(crate::ChromaError::AdapterNotFound)?;
+
+        let (device, queue) = adapter
+            .request_device(&self.device_descriptor.unwrap_or_default(), None)
+            .await?;
+
+        let capabilities = surface.get_capabilities(&adapter);
+        let texture_format = resolve_texture_format(self.texture_format, &capabilities.formats)?;
+
+        let (width, height) = self.pixel_size;
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("chroma-pixels-texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: texture_format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        surface.configure(
+            &device,
+            &wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: texture_format,
+                width: size.width,
+                height: size.height,
+                present_mode: self.present_mode,
+                alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                view_formats: vec![],
+                desired_maximum_frame_latency: 2,
+            },
+        );
+
+        let scaling_renderer = ScalingRenderer::new(
+            &device,
+            &texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            self.pixel_size,
+            (size.width, size.height),
+            texture_format,
+            self.clear_color,
+            self.blend_state,
+        );
+
+        let msaa_texture = (self.msaa_samples > 1).then(|| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("chroma-pixels-msaa-texture"),
+                size: wgpu::Extent3d {
+                    width: size.width,
+                    height: size.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: self.msaa_samples,
+                dimension: wgpu::TextureDimension::D2,
+                format: texture_format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+        });
+
+        let context = PixelsContext {
+            device,
+            queue,
+            texture,
+            texture_format,
+            msaa_samples: self.msaa_samples,
+            msaa_texture,
+        };
+
+        Ok(Pixels::from_parts(
+            context,
+            surface,
+            scaling_renderer,
+            size,
+            self.pixel_size,
+            self.present_mode,
+            self.clear_color,
+            self.blend_state,
+        ))
+    }
+}
+
+/// Resolves the builder's requested texture format against what the surface
+/// actually supports: an explicit `requested` format must appear in
+/// `available` or `build` fails naming the alternatives, while `None` falls
+/// back to [`crate::surface_format::choose_surface_format`]'s
+/// sRGB-preferring auto-detection.
+fn resolve_texture_format(
+    requested: Option<wgpu::TextureFormat>,
+    available: &[wgpu::TextureFormat],
+) -> Result<wgpu::TextureFormat, crate::ChromaError> {
+    match requested {
+        Some(format) if available.contains(&format) => Ok(format),
+        Some(format) => Err(crate::ChromaError::UnsupportedTextureFormat {
+            requested: format,
+            available: available.to_vec(),
+        }),
+        None => Ok(crate::surface_format::choose_surface_format(available)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_detect_falls_back_to_first_format_when_no_srgb_available() {
+        let available = [wgpu::TextureFormat::Bgra8Unorm, wgpu::TextureFormat::Rgba8Unorm];
+        assert_eq!(
+            resolve_texture_format(None, &available).unwrap(),
+            wgpu::TextureFormat::Bgra8Unorm
+        );
+    }
+
+    #[test]
+    fn explicit_request_errors_when_unsupported() {
+        let available = [wgpu::TextureFormat::Bgra8Unorm];
+        let err = resolve_texture_format(Some(wgpu::TextureFormat::Rgba8UnormSrgb), &available)
+            .unwrap_err();
+        assert!(matches!(err, crate::ChromaError::UnsupportedTextureFormat { .. }));
+    }
+
+    #[test]
+    fn explicit_request_passes_through_when_supported() {
+        let available = [wgpu::TextureFormat::Bgra8Unorm, wgpu::TextureFormat::Rgba8UnormSrgb];
+        assert_eq!(
+            resolve_texture_format(Some(wgpu::TextureFormat::Rgba8UnormSrgb), &available).unwrap(),
+            wgpu::TextureFormat::Rgba8UnormSrgb
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use super::BitmapFont;
+
+const GLYPH_SIZE: u32 = 8;
+
+/// Each glyph is 8 rows, one byte per row, bit 7 = leftmost column. Covers
+/// digits, uppercase letters, and the punctuation a debug overlay (an FPS
+/// counter, a position readout) actually needs — not the full ASCII range.
+const GLYPHS: &[(char, [u8; 8])] = &[
+    (' ', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('0', [0x78, 0x84, 0x8c, 0x94, 0xa4, 0xc4, 0x78, 0x00]),
+    ('1', [0x20, 0x60, 0x20, 0x20, 0x20, 0x20, 0x70, 0x00]),
+    ('2', [0x78, 0x84, 0x04, 0x08, 0x10, 0x20, 0xfc, 0x00]),
+    ('3', [0x78, 0x84, 0x04, 0x18, 0x04, 0x84, 0x78, 0x00]),
+    ('4', [0x08, 0x18, 0x28, 0x48, 0xfc, 0x08, 0x08, 0x00]),
+    ('5', [0xfc, 0x80, 0xf8, 0x0c, 0x04, 0x84, 0x78, 0x00]),
+    ('6', [0x38, 0x40, 0x80, 0xf8, 0x84, 0x84, 0x78, 0x00]),
+    ('7', [0xfc, 0x04, 0x08, 0x10, 0x20, 0x20, 0x20, 0x00]),
+    ('8', [0x78, 0x84, 0x84, 0x78, 0x84, 0x84, 0x78, 0x00]),
+    ('9', [0x78, 0x84, 0x84, 0x7c, 0x04, 0x08, 0x30, 0x00]),
+    ('A', [0x20, 0x50, 0x88, 0xf8, 0x88, 0x88, 0x88, 0x00]),
+    ('B', [0xf8, 0x84, 0x84, 0xf8, 0x84, 0x84, 0xf8, 0x00]),
+    ('C', [0x78, 0x84, 0x80, 0x80, 0x80, 0x84, 0x78, 0x00]),
+    ('D', [0xf0, 0x88, 0x84, 0x84, 0x84, 0x88, 0xf0, 0x00]),
+    ('E', [0xfc, 0x80, 0x80, 0xf8, 0x80, 0x80, 0xfc, 0x00]),
+    ('F', [0xfc, 0x80, 0x80, 0xf8, 0x80, 0x80, 0x80, 0x00]),
+    ('G', [0x78, 0x84, 0x80, 0x9c, 0x84, 0x84, 0x78, 0x00]),
+    ('H', [0x84, 0x84, 0x84, 0xfc, 0x84, 0x84, 0x84, 0x00]),
+    ('I', [0x70, 0x20, 0x20, 0x20, 0x20, 0x20, 0x70, 0x00]),
+    ('J', [0x18, 0x08, 0x08, 0x08, 0x88, 0x88, 0x70, 0x00]),
+    ('K', [0x84, 0x88, 0x90, 0xe0, 0x90, 0x88, 0x84, 0x00]),
+    ('L', [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xfc, 0x00]),
+    ('M', [0x82, 0xc6, 0xaa, 0x92, 0x82, 0x82, 0x82, 0x00]),
+    ('N', [0x84, 0xc4, 0xa4, 0x94, 0x8c, 0x84, 0x84, 0x00]),
+    ('O', [0x78, 0x84, 0x84, 0x84, 0x84, 0x84, 0x78, 0x00]),
+    ('P', [0xf8, 0x84, 0x84, 0xf8, 0x80, 0x80, 0x80, 0x00]),
+    ('Q', [0x78, 0x84, 0x84, 0x84, 0x94, 0x88, 0x7a, 0x00]),
+    ('R', [0xf8, 0x84, 0x84, 0xf8, 0x90, 0x88, 0x84, 0x00]),
+    ('S', [0x78, 0x84, 0x80, 0x78, 0x04, 0x84, 0x78, 0x00]),
+    ('T', [0xfe, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00]),
+    ('U', [0x84, 0x84, 0x84, 0x84, 0x84, 0x84, 0x78, 0x00]),
+    ('V', [0x84, 0x84, 0x84, 0x84, 0x48, 0x48, 0x30, 0x00]),
+    ('W', [0x82, 0x82, 0x82, 0x92, 0xaa, 0xc6, 0x82, 0x00]),
+    ('X', [0x84, 0x48, 0x30, 0x30, 0x48, 0x84, 0x84, 0x00]),
+    ('Y', [0x84, 0x48, 0x30, 0x10, 0x10, 0x10, 0x10, 0x00]),
+    ('Z', [0xfc, 0x04, 0x08, 0x10, 0x20, 0x40, 0xfc, 0x00]),
+    ('.', [0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x30, 0x00]),
+    (',', [0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x20, 0x40]),
+    (':', [0x00, 0x30, 0x30, 0x00, 0x30, 0x30, 0x00, 0x00]),
+    ('!', [0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x20, 0x00]),
+    ('-', [0x00, 0x00, 0x00, 0xfc, 0x00, 0x00, 0x00, 0x00]),
+    ('?', [0x78, 0x84, 0x04, 0x08, 0x20, 0x00, 0x20, 0x00]),
+];
+
+/// Builds the sheet and cell map for [`BitmapFont::debug_font`]. Glyphs are
+/// laid out in a single row, one 8x8 cell per entry in [`GLYPHS`], with an
+/// extra blank cell appended for the fallback glyph.
+pub(super) fn build() -> BitmapFont {
+    let cell_count = GLYPHS.len() as u32 + 1;
+    let sheet_width = cell_count * GLYPH_SIZE;
+    let mut sheet = vec![0u8; (sheet_width * GLYPH_SIZE * 4) as usize];
+    let mut glyphs = HashMap::with_capacity(GLYPHS.len());
+
+    for (col, &(ch, rows)) in GLYPHS.iter().enumerate() {
+        let col = col as u32;
+        for (row, bits) in rows.iter().enumerate() {
+            for bit in 0..GLYPH_SIZE {
+                let lit = (bits >> (7 - bit)) & 1 == 1;
+                let x = col * GLYPH_SIZE + bit;
+                let y = row as u32;
+                let offset = ((y * sheet_width + x) * 4 + 3) as usize;
+                sheet[offset] = if lit { 255 } else { 0 };
+            }
+        }
+        glyphs.insert(ch, (col, 0));
+    }
+
+    let fallback_glyph = (GLYPHS.len() as u32, 0);
+    BitmapFont::new(sheet, sheet_width, GLYPH_SIZE, GLYPH_SIZE, glyphs, fallback_glyph)
+}
@@ -0,0 +1,1204 @@
+use super::BitmapFont;
+
+/// Options for [`FrameBuffer::blit`] and [`FrameBuffer::blit_blend`].
+/// `src_rect` restricts the copy to a sub-rectangle of the source image (in
+/// the source's own coordinates); `None` uses the whole image. `flip_x`/
+/// `flip_y` mirror the copied region in place, for sprite-sheet variants
+/// that share art.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlitOptions {
+    pub src_rect: Option<(u32, u32, u32, u32)>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+/// The CPU-side 8-bit-per-channel pixel buffer backing a [`super::Pixels`]
+/// instance, with `(0, 0)` at the top-left. Bytes are stored in whatever
+/// channel order [`super::Pixels::frame_format`] reports (RGBA or BGRA);
+/// the blending and drawing math here is channel-order-agnostic since alpha
+/// always stays in the last slot. Kept separate from the GPU handles so its
+/// pixel math is unit-testable without a device.
+#[derive(Debug, Clone)]
+pub(crate) struct FrameBuffer {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+    /// The bounding box, `(x0, y0, x1, y1)` exclusive, of every pixel
+    /// touched since the last [`FrameBuffer::clear_dirty`]. Tracking one
+    /// box rather than a list of merged rects is a deliberate
+    /// simplification: it never under-uploads, and a game's draw calls in a
+    /// given frame are usually clustered (a HUD corner, a moving sprite)
+    /// rather than scattered, so the box rarely covers much more than the
+    /// true dirty area.
+    dirty: Option<(u32, u32, u32, u32)>,
+}
+
+impl FrameBuffer {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; (width * height * 4) as usize],
+            dirty: None,
+        }
+    }
+
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Raw mutable access to the buffer. Conservatively marks the entire
+    /// frame dirty, since callers writing through this slice bypass every
+    /// other tracked method.
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.mark_dirty(0, 0, self.width, self.height);
+        &mut self.data
+    }
+
+    /// The bounding box, `(x0, y0, x1, y1)` exclusive, of every pixel
+    /// touched since the last [`FrameBuffer::clear_dirty`], or `None` if
+    /// nothing has changed.
+    pub(crate) fn dirty_rect(&self) -> Option<(u32, u32, u32, u32)> {
+        self.dirty
+    }
+
+    /// Resets the tracked dirty rectangle, e.g. once it's been uploaded to
+    /// the GPU.
+    pub(crate) fn clear_dirty(&mut self) {
+        self.dirty = None;
+    }
+
+    /// Widens the tracked dirty rectangle to include `(x0, y0, x1, y1)`
+    /// (exclusive), which callers must have already clipped to the buffer
+    /// bounds.
+    fn mark_dirty(&mut self, x0: u32, y0: u32, x1: u32, y1: u32) {
+        if x0 >= x1 || y0 >= y1 {
+            return;
+        }
+        self.dirty = Some(match self.dirty {
+            Some((dx0, dy0, dx1, dy1)) => (dx0.min(x0), dy0.min(y0), dx1.max(x1), dy1.max(y1)),
+            None => (x0, y0, x1, y1),
+        });
+    }
+
+    /// Resizes the buffer to `new_width` by `new_height`, preserving old
+    /// contents top-left aligned where the two sizes overlap and filling
+    /// any newly exposed area with transparent black. Marks the whole
+    /// buffer dirty, since the layout changed regardless of which pixels
+    /// kept their color.
+    pub(crate) fn resize(&mut self, new_width: u32, new_height: u32) {
+        let mut new_data = vec![0; (new_width * new_height * 4) as usize];
+        let copy_width = self.width.min(new_width);
+        let copy_height = self.height.min(new_height);
+        let row_bytes = (copy_width * 4) as usize;
+        for y in 0..copy_height {
+            let src_offset = ((y * self.width) * 4) as usize;
+            let dst_offset = ((y * new_width) * 4) as usize;
+            new_data[dst_offset..dst_offset + row_bytes]
+                .copy_from_slice(&self.data[src_offset..src_offset + row_bytes]);
+        }
+
+        self.width = new_width;
+        self.height = new_height;
+        self.data = new_data;
+        self.mark_dirty(0, 0, new_width, new_height);
+    }
+
+    pub(crate) fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        if let Some(offset) = self.offset(x, y) {
+            self.data[offset..offset + 4].copy_from_slice(&color);
+            self.mark_dirty(x, y, x + 1, y + 1);
+        }
+    }
+
+    pub(crate) fn get_pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        self.offset(x, y)
+            .map(|offset| self.data[offset..offset + 4].try_into().unwrap())
+    }
+
+    /// Like [`FrameBuffer::set_pixel`], but skips the bounds check. Callers
+    /// must guarantee `x < width` and `y < height`.
+    pub(crate) fn set_pixel_unchecked(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let offset = ((y * self.width + x) * 4) as usize;
+        self.data[offset..offset + 4].copy_from_slice(&color);
+        self.mark_dirty(x, y, x + 1, y + 1);
+    }
+
+    /// Mutable access to row `y`, as `width * 4` bytes. Marks the whole row
+    /// dirty, since writes through the slice bypass per-pixel dirty
+    /// tracking. `None` if `y >= height`. Recomputed from the current
+    /// `width`/`height` on every call, so it stays correct across
+    /// [`FrameBuffer::resize`].
+    pub(crate) fn row_mut(&mut self, y: u32) -> Option<&mut [u8]> {
+        if y >= self.height {
+            return None;
+        }
+        self.mark_dirty(0, y, self.width, y + 1);
+        let stride = (self.width * 4) as usize;
+        let offset = y as usize * stride;
+        Some(&mut self.data[offset..offset + stride])
+    }
+
+    /// Mutable access to every row in top-to-bottom order, each as `width *
+    /// 4` bytes. Marks the whole buffer dirty, for the same reason as
+    /// [`FrameBuffer::row_mut`].
+    pub(crate) fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        self.mark_dirty(0, 0, self.width, self.height);
+        let stride = (self.width * 4) as usize;
+        self.data.chunks_mut(stride)
+    }
+
+    /// Overwrites every pixel with `color`. Faster than looping
+    /// [`FrameBuffer::set_pixel`] since it writes straight into the backing
+    /// buffer instead of bounds-checking and dirty-tracking one pixel at a
+    /// time.
+    pub(crate) fn fill(&mut self, color: [u8; 4]) {
+        for pixel in self.data.chunks_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+        self.mark_dirty(0, 0, self.width, self.height);
+    }
+
+    /// Clones the current buffer contents, for a caller wanting to diff
+    /// frames or implement undo without holding a live borrow of the buffer.
+    pub(crate) fn snapshot(&self) -> Vec<u8> {
+        self.data.clone()
+    }
+
+    /// Overwrites the buffer with a previously taken
+    /// [`FrameBuffer::snapshot`]. Errors with
+    /// [`crate::ChromaError::InvalidSnapshotLength`] instead of copying if
+    /// `data`'s length doesn't match the current buffer size — e.g. a
+    /// snapshot taken before a [`FrameBuffer::resize`] — rather than
+    /// panicking or copying a truncated/overrun slice.
+    pub(crate) fn restore(&mut self, data: &[u8]) -> Result<(), crate::ChromaError> {
+        if data.len() != self.data.len() {
+            return Err(crate::ChromaError::InvalidSnapshotLength {
+                expected: self.data.len(),
+                actual: data.len(),
+            });
+        }
+        self.data.copy_from_slice(data);
+        self.mark_dirty(0, 0, self.width, self.height);
+        Ok(())
+    }
+
+    /// Draws a line from `(x0, y0)` to `(x1, y1)` with Bresenham's
+    /// algorithm, clipping any portion outside the buffer rather than
+    /// panicking. Signed coordinates so off-screen endpoints are natural.
+    pub(crate) fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: [u8; 4]) {
+        let dx = (x1 - x0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let dy = -(y1 - y0).abs();
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut error = dx + dy;
+
+        let (mut x, mut y) = (x0, y0);
+        loop {
+            if x >= 0 && y >= 0 {
+                self.set_pixel(x as u32, y as u32, color);
+            }
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Fills the rectangle `(x, y, w, h)` with `color`, clipped to the
+    /// buffer bounds. Uses row-wise `copy_from_slice` rather than
+    /// per-pixel writes so large fills stay fast.
+    pub(crate) fn fill_rect(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 4]) {
+        let Some((x0, y0, x1, y1)) = self.clip_rect(x, y, w, h) else {
+            return;
+        };
+
+        let mut row = Vec::with_capacity(((x1 - x0) * 4) as usize);
+        for _ in x0..x1 {
+            row.extend_from_slice(&color);
+        }
+
+        for row_y in y0..y1 {
+            let start = self.offset(x0, row_y).unwrap();
+            self.data[start..start + row.len()].copy_from_slice(&row);
+        }
+        self.mark_dirty(x0, y0, x1, y1);
+    }
+
+    /// Draws the 1px outline of the rectangle `(x, y, w, h)`, clipped to
+    /// the buffer bounds.
+    pub(crate) fn draw_rect(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 4]) {
+        if w == 0 || h == 0 {
+            return;
+        }
+        let (x0, y0) = (x as i32, y as i32);
+        let (x1, y1) = (x as i32 + w as i32 - 1, y as i32 + h as i32 - 1);
+
+        self.draw_line(x0, y0, x1, y0, color);
+        self.draw_line(x0, y1, x1, y1, color);
+        self.draw_line(x0, y0, x0, y1, color);
+        self.draw_line(x1, y0, x1, y1, color);
+    }
+
+    /// Like [`FrameBuffer::fill_rect`], but src-over alpha-blends `color`
+    /// against the existing pixels instead of overwriting them. Useful for
+    /// HUD bars and dimming overlays.
+    pub(crate) fn fill_rect_blend(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 4]) {
+        let Some((x0, y0, x1, y1)) = self.clip_rect(x, y, w, h) else {
+            return;
+        };
+
+        for row_y in y0..y1 {
+            for col_x in x0..x1 {
+                let existing = self.get_pixel(col_x, row_y).unwrap();
+                self.set_pixel(col_x, row_y, blend_src_over(existing, color));
+            }
+        }
+    }
+
+    /// Draws the outline of a circle centered at `(cx, cy)` with radius `r`
+    /// using the midpoint circle algorithm, clipped to the buffer bounds.
+    /// Radius 0 draws a single pixel.
+    pub(crate) fn draw_circle(&mut self, cx: i32, cy: i32, r: i32, color: [u8; 4]) {
+        for (x, y) in midpoint_circle_points(cx, cy, r) {
+            if x >= 0 && y >= 0 {
+                self.set_pixel(x as u32, y as u32, color);
+            }
+        }
+    }
+
+    /// Fills a circle centered at `(cx, cy)` with radius `r`, clipped to the
+    /// buffer bounds. Radius 0 draws a single pixel.
+    pub(crate) fn fill_circle(&mut self, cx: i32, cy: i32, r: i32, color: [u8; 4]) {
+        self.fill_ellipse(cx, cy, r, r, color);
+    }
+
+    /// Draws the outline of an ellipse centered at `(cx, cy)` with radii
+    /// `(rx, ry)`, clipped to the buffer bounds.
+    pub(crate) fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: [u8; 4]) {
+        for (x, y) in midpoint_ellipse_points(cx, cy, rx, ry) {
+            if x >= 0 && y >= 0 {
+                self.set_pixel(x as u32, y as u32, color);
+            }
+        }
+    }
+
+    /// Fills an ellipse centered at `(cx, cy)` with radii `(rx, ry)`,
+    /// clipped to the buffer bounds. Computes each scanline's half-width
+    /// directly from the ellipse equation rather than the outline's
+    /// midpoint decision variable, so filled rows have no gaps.
+    pub(crate) fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: [u8; 4]) {
+        if rx == 0 && ry == 0 {
+            if cx >= 0 && cy >= 0 {
+                self.set_pixel(cx as u32, cy as u32, color);
+            }
+            return;
+        }
+        if rx == 0 || ry == 0 {
+            let x0 = cx - rx;
+            let x1 = cx + rx;
+            let y0 = cy - ry;
+            let y1 = cy + ry;
+            for y in y0..=y1 {
+                for x in x0..=x1 {
+                    if x >= 0 && y >= 0 {
+                        self.set_pixel(x as u32, y as u32, color);
+                    }
+                }
+            }
+            return;
+        }
+
+        for dy in -ry..=ry {
+            let ratio = 1.0 - (dy * dy) as f64 / (ry * ry) as f64;
+            let dx = ellipse_half_width(rx, ratio);
+            let y = cy + dy;
+            if y < 0 {
+                continue;
+            }
+            for x in (cx - dx)..=(cx + dx) {
+                if x >= 0 {
+                    self.set_pixel(x as u32, y as u32, color);
+                }
+            }
+        }
+    }
+
+    /// Copies `src`, an RGBA8 image `src_w` by `src_h`, into this buffer at
+    /// `(x, y)`, clipping any rows/columns that fall outside the
+    /// destination. `x`/`y` may be negative.
+    pub(crate) fn blit(&mut self, x: i32, y: i32, src: &[u8], src_w: u32, src_h: u32, options: BlitOptions) {
+        let Some((sx0, sy0, sw, sh)) = clipped_src_rect(options.src_rect, src_w, src_h) else {
+            return;
+        };
+
+        if !options.flip_x && !options.flip_y {
+            // Fast path: with no flips, each destination row is a
+            // contiguous run of source pixels, so it can be copied with one
+            // `copy_from_slice` instead of a pixel-at-a-time loop.
+            let mut dirty: Option<(u32, u32, u32, u32)> = None;
+            for row in 0..sh {
+                let dst_y = y + row as i32;
+                if dst_y < 0 || dst_y as u32 >= self.height {
+                    continue;
+                }
+                let Some((dst_x, col_offset, run)) = clip_row(x, sw, self.width) else {
+                    continue;
+                };
+                let src_offset = (((sy0 + row) * src_w + sx0 + col_offset) * 4) as usize;
+                let dst_offset = self.offset(dst_x, dst_y as u32).unwrap();
+                let byte_len = run as usize * 4;
+                self.data[dst_offset..dst_offset + byte_len]
+                    .copy_from_slice(&src[src_offset..src_offset + byte_len]);
+
+                let dst_y = dst_y as u32;
+                dirty = Some(match dirty {
+                    Some((x0, y0, x1, y1)) => (x0.min(dst_x), y0.min(dst_y), x1.max(dst_x + run), y1.max(dst_y + 1)),
+                    None => (dst_x, dst_y, dst_x + run, dst_y + 1),
+                });
+            }
+            if let Some((x0, y0, x1, y1)) = dirty {
+                self.mark_dirty(x0, y0, x1, y1);
+            }
+            return;
+        }
+
+        self.blit_pixels(x, y, src, src_w, sx0, sy0, sw, sh, options, |_dst, src| src);
+    }
+
+    /// Like [`FrameBuffer::blit`], but src-over alpha-blends each copied
+    /// pixel against the existing one instead of overwriting it.
+    pub(crate) fn blit_blend(&mut self, x: i32, y: i32, src: &[u8], src_w: u32, src_h: u32, options: BlitOptions) {
+        let Some((sx0, sy0, sw, sh)) = clipped_src_rect(options.src_rect, src_w, src_h) else {
+            return;
+        };
+        self.blit_pixels(x, y, src, src_w, sx0, sy0, sw, sh, options, blend_src_over);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn blit_pixels(
+        &mut self,
+        x: i32,
+        y: i32,
+        src: &[u8],
+        src_w: u32,
+        sx0: u32,
+        sy0: u32,
+        sw: u32,
+        sh: u32,
+        options: BlitOptions,
+        blend: impl Fn([u8; 4], [u8; 4]) -> [u8; 4],
+    ) {
+        for row in 0..sh {
+            let dst_y = if options.flip_y { y + (sh - 1 - row) as i32 } else { y + row as i32 };
+            if dst_y < 0 || dst_y as u32 >= self.height {
+                continue;
+            }
+            for col in 0..sw {
+                let dst_x = if options.flip_x { x + (sw - 1 - col) as i32 } else { x + col as i32 };
+                if dst_x < 0 || dst_x as u32 >= self.width {
+                    continue;
+                }
+                let src_offset = (((sy0 + row) * src_w + sx0 + col) * 4) as usize;
+                let pixel: [u8; 4] = src[src_offset..src_offset + 4].try_into().unwrap();
+                let existing = self.get_pixel(dst_x as u32, dst_y as u32).unwrap();
+                self.set_pixel_unchecked(dst_x as u32, dst_y as u32, blend(existing, pixel));
+            }
+        }
+    }
+
+    /// Draws `text` starting at `(x, y)` using `font`, tinted with `color`
+    /// (the font's RGB is discarded; only its alpha mask is used). Newlines
+    /// advance the cursor by the font's glyph height and reset `x`; text
+    /// running off the right or bottom edge clips rather than wrapping.
+    pub(crate) fn draw_text(&mut self, x: i32, y: i32, text: &str, font: &BitmapFont, color: [u8; 4]) {
+        let (glyph_w, glyph_h) = (font.glyph_width(), font.glyph_height());
+        let mut cursor_x = x;
+        let mut cursor_y = y;
+
+        for ch in text.chars() {
+            if ch == '\n' {
+                cursor_x = x;
+                cursor_y += glyph_h as i32;
+                continue;
+            }
+
+            let cell = font.cell_for(ch);
+            for local_y in 0..glyph_h {
+                let dst_y = cursor_y + local_y as i32;
+                if dst_y < 0 || dst_y as u32 >= self.height {
+                    continue;
+                }
+                for local_x in 0..glyph_w {
+                    let dst_x = cursor_x + local_x as i32;
+                    if dst_x < 0 || dst_x as u32 >= self.width {
+                        continue;
+                    }
+                    let alpha = font.alpha_at(cell, local_x, local_y);
+                    if alpha == 0 {
+                        continue;
+                    }
+                    let existing = self.get_pixel(dst_x as u32, dst_y as u32).unwrap();
+                    let tinted = [color[0], color[1], color[2], alpha];
+                    self.set_pixel_unchecked(dst_x as u32, dst_y as u32, blend_src_over(existing, tinted));
+                }
+            }
+            cursor_x += glyph_w as i32;
+        }
+    }
+
+    /// Clips `(x, y, w, h)` to the buffer bounds, returning
+    /// `(x0, y0, x1, y1)` (exclusive) or `None` if the result is empty.
+    fn clip_rect(&self, x: u32, y: u32, w: u32, h: u32) -> Option<(u32, u32, u32, u32)> {
+        if w == 0 || h == 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        let x1 = (x + w).min(self.width);
+        let y1 = (y + h).min(self.height);
+        Some((x, y, x1, y1))
+    }
+
+    fn offset(&self, x: u32, y: u32) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(((y * self.width + x) * 4) as usize)
+        } else {
+            None
+        }
+    }
+}
+
+fn blend_src_over(dst: [u8; 4], src: [u8; 4]) -> [u8; 4] {
+    let alpha = src[3] as f32 / 255.0;
+    let blend = |d: u8, s: u8| -> u8 { (s as f32 * alpha + d as f32 * (1.0 - alpha)).round() as u8 };
+    [
+        blend(dst[0], src[0]),
+        blend(dst[1], src[1]),
+        blend(dst[2], src[2]),
+        (src[3] as f32 + dst[3] as f32 * (1.0 - alpha)).round() as u8,
+    ]
+}
+
+/// Clips a `src_rect` (or the full `src_w` by `src_h` image if `None`)
+/// against the source image's own bounds, returning `(x, y, w, h)` or
+/// `None` if the requested rectangle doesn't overlap the source at all.
+fn clipped_src_rect(src_rect: Option<(u32, u32, u32, u32)>, src_w: u32, src_h: u32) -> Option<(u32, u32, u32, u32)> {
+    let (sx0, sy0, sw, sh) = src_rect.unwrap_or((0, 0, src_w, src_h));
+    if sw == 0 || sh == 0 || sx0 >= src_w || sy0 >= src_h {
+        return None;
+    }
+    Some((sx0, sy0, sw.min(src_w - sx0), sh.min(src_h - sy0)))
+}
+
+/// Clips a destination row starting at `x` with length `len` against
+/// `[0, bound)`, returning `(dst_x, src_col_offset, run_length)`, or `None`
+/// if the row falls entirely outside the bound.
+fn clip_row(x: i32, len: u32, bound: u32) -> Option<(u32, u32, u32)> {
+    if len == 0 {
+        return None;
+    }
+    let x1 = x + len as i32;
+    if x1 <= 0 || x >= bound as i32 {
+        return None;
+    }
+    let clipped_x0 = x.max(0);
+    let clipped_x1 = x1.min(bound as i32);
+    let run = (clipped_x1 - clipped_x0) as u32;
+    let col_offset = (clipped_x0 - x) as u32;
+    Some((clipped_x0 as u32, col_offset, run))
+}
+
+/// The boundary offsets `(dx, dy)`, `dx >= dy >= 0`, of a midpoint-circle
+/// octant with radius `r`. The other seven octants are this one reflected.
+fn midpoint_circle_octant(r: i32) -> Vec<(i32, i32)> {
+    let mut octant = Vec::new();
+    let mut x = r;
+    let mut y = 0;
+    let mut error = 0;
+    loop {
+        octant.push((x, y));
+        if x < y {
+            break;
+        }
+        y += 1;
+        if error <= 0 {
+            error += 2 * y + 1;
+        }
+        if error > 0 {
+            x -= 1;
+            error -= 2 * x + 1;
+        }
+    }
+    octant
+}
+
+/// The pixels on the outline of a circle centered at `(cx, cy)` with radius
+/// `r`, via the midpoint circle algorithm. Radius 0 is a single pixel.
+fn midpoint_circle_points(cx: i32, cy: i32, r: i32) -> Vec<(i32, i32)> {
+    if r == 0 {
+        return vec![(cx, cy)];
+    }
+    let mut points = Vec::new();
+    for (x, y) in midpoint_circle_octant(r) {
+        for (dx, dy) in [
+            (x, y),
+            (y, x),
+            (-y, x),
+            (-x, y),
+            (-x, -y),
+            (-y, -x),
+            (y, -x),
+            (x, -y),
+        ] {
+            points.push((cx + dx, cy + dy));
+        }
+    }
+    points
+}
+
+/// The pixels on the outline of an ellipse centered at `(cx, cy)` with radii
+/// `(rx, ry)`, via the two-region midpoint ellipse algorithm. All
+/// intermediate quantities are scaled by 4 to keep the arithmetic integer
+/// despite the algorithm's `x + 0.5` and `rx^2 / 4` terms.
+fn midpoint_ellipse_points(cx: i32, cy: i32, rx: i32, ry: i32) -> Vec<(i32, i32)> {
+    if rx == 0 || ry == 0 {
+        let (x0, x1) = (cx - rx, cx + rx);
+        let (y0, y1) = (cy - ry, cy + ry);
+        let mut points = Vec::new();
+        for x in x0..=x1 {
+            for y in y0..=y1 {
+                points.push((x, y));
+            }
+        }
+        return points;
+    }
+
+    let (rx, ry) = (rx as i64, ry as i64);
+    let (rx2, ry2) = (rx * rx, ry * ry);
+    let (cx, cy) = (cx as i64, cy as i64);
+
+    let mut points = Vec::new();
+    let push = |points: &mut Vec<(i32, i32)>, x: i64, y: i64| {
+        points.push(((cx + x) as i32, (cy + y) as i32));
+        points.push(((cx - x) as i32, (cy + y) as i32));
+        points.push(((cx + x) as i32, (cy - y) as i32));
+        points.push(((cx - x) as i32, (cy - y) as i32));
+    };
+
+    let mut x: i64 = 0;
+    let mut y: i64 = ry;
+    push(&mut points, x, y);
+
+    let mut dx = 2 * ry2 * x;
+    let mut dy = 2 * rx2 * y;
+
+    // Region 1: slope magnitude < 1, stepping x.
+    let mut d1 = 4 * ry2 - 4 * rx2 * ry + rx2;
+    while dx < dy {
+        x += 1;
+        dx += 2 * ry2;
+        if d1 < 0 {
+            d1 += 4 * dx + 4 * ry2;
+        } else {
+            y -= 1;
+            dy -= 2 * rx2;
+            d1 += 4 * dx - 4 * dy + 4 * ry2;
+        }
+        push(&mut points, x, y);
+    }
+
+    // Region 2: slope magnitude >= 1, stepping y.
+    let mut d2 = ry2 * (2 * x + 1) * (2 * x + 1) + 4 * rx2 * (y - 1) * (y - 1) - 4 * rx2 * ry2;
+    while y > 0 {
+        y -= 1;
+        dy -= 2 * rx2;
+        if d2 > 0 {
+            d2 += 4 * rx2 - 4 * dy;
+        } else {
+            x += 1;
+            dx += 2 * ry2;
+            d2 += 4 * dx - 4 * dy + 4 * rx2;
+        }
+        push(&mut points, x, y);
+    }
+
+    points
+}
+
+/// The largest `dx` such that `(dx / rx)^2 <= ratio`, i.e. the half-width of
+/// an ellipse scanline at a given height ratio. Corrects for `f64` rounding
+/// so the result matches the exact integer comparison.
+fn ellipse_half_width(rx: i32, ratio: f64) -> i32 {
+    if ratio <= 0.0 {
+        return 0;
+    }
+    let limit = ratio * (rx * rx) as f64;
+    let mut dx = ((rx as f64) * ratio.sqrt()).floor() as i32;
+    while ((dx + 1) as f64).powi(2) <= limit + 1e-9 {
+        dx += 1;
+    }
+    while (dx as f64).powi(2) > limit + 1e-9 {
+        dx -= 1;
+    }
+    dx.max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_get_pixel_round_trips() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.set_pixel(3, 3, [1, 2, 3, 4]);
+        assert_eq!(buffer.get_pixel(3, 3), Some([1, 2, 3, 4]));
+        assert_eq!(buffer.get_pixel(0, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn fresh_buffer_has_no_dirty_rect() {
+        let buffer = FrameBuffer::new(4, 4);
+        assert_eq!(buffer.dirty_rect(), None);
+    }
+
+    #[test]
+    fn fill_rect_schedules_only_the_filled_bytes_for_upload() {
+        let mut buffer = FrameBuffer::new(256, 224);
+        buffer.fill_rect(10, 10, 10, 10, [255, 255, 255, 255]);
+        let (x0, y0, x1, y1) = buffer.dirty_rect().expect("fill_rect should mark pixels dirty");
+        assert_eq!((x0, y0, x1, y1), (10, 10, 20, 20));
+
+        let scheduled_bytes = (x1 - x0) as usize * (y1 - y0) as usize * 4;
+        assert_eq!(scheduled_bytes, 400);
+    }
+
+    #[test]
+    fn clear_dirty_resets_tracking() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.set_pixel(0, 0, [1, 1, 1, 1]);
+        buffer.clear_dirty();
+        assert_eq!(buffer.dirty_rect(), None);
+    }
+
+    #[test]
+    fn dirty_rect_grows_to_cover_every_touched_pixel() {
+        let mut buffer = FrameBuffer::new(16, 16);
+        buffer.set_pixel(2, 2, [1, 1, 1, 1]);
+        buffer.set_pixel(10, 5, [1, 1, 1, 1]);
+        assert_eq!(buffer.dirty_rect(), Some((2, 2, 11, 6)));
+    }
+
+    #[test]
+    fn as_mut_slice_conservatively_marks_the_whole_frame_dirty() {
+        let mut buffer = FrameBuffer::new(8, 6);
+        let _ = buffer.as_mut_slice();
+        assert_eq!(buffer.dirty_rect(), Some((0, 0, 8, 6)));
+    }
+
+    #[test]
+    fn row_mut_slices_exactly_one_row() {
+        let mut buffer = FrameBuffer::new(4, 3);
+        buffer.row_mut(1).unwrap().fill(9);
+        for x in 0..4 {
+            assert_eq!(buffer.get_pixel(x, 1), Some([9, 9, 9, 9]));
+        }
+        assert_eq!(buffer.get_pixel(0, 0), Some([0, 0, 0, 0]));
+        assert_eq!(buffer.get_pixel(0, 2), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn row_mut_out_of_bounds_returns_none() {
+        let mut buffer = FrameBuffer::new(4, 3);
+        assert!(buffer.row_mut(3).is_none());
+    }
+
+    #[test]
+    fn row_mut_stays_correct_after_resize() {
+        let mut buffer = FrameBuffer::new(4, 3);
+        buffer.resize(8, 2);
+        assert_eq!(buffer.row_mut(1).unwrap().len(), 8 * 4);
+        assert!(buffer.row_mut(2).is_none());
+    }
+
+    #[test]
+    fn rows_mut_covers_every_row_in_order() {
+        let mut buffer = FrameBuffer::new(2, 3);
+        for (y, row) in buffer.rows_mut().enumerate() {
+            row.fill(y as u8);
+        }
+        assert_eq!(buffer.get_pixel(0, 0), Some([0, 0, 0, 0]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([1, 1, 1, 1]));
+        assert_eq!(buffer.get_pixel(0, 2), Some([2, 2, 2, 2]));
+    }
+
+    #[test]
+    fn fill_overwrites_every_pixel_and_marks_it_all_dirty() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.fill([1, 2, 3, 4]);
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(buffer.get_pixel(x, y), Some([1, 2, 3, 4]));
+            }
+        }
+        assert_eq!(buffer.dirty_rect(), Some((0, 0, 4, 4)));
+    }
+
+    #[test]
+    fn blit_marks_only_the_copied_region_dirty() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.blit(1, 1, &BLIT_SRC, 2, 2, BlitOptions::default());
+        assert_eq!(buffer.dirty_rect(), Some((1, 1, 3, 3)));
+    }
+
+    #[test]
+    fn out_of_bounds_pixel_access_is_a_no_op() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.set_pixel(4, 0, [9, 9, 9, 9]);
+        assert_eq!(buffer.get_pixel(4, 0), None);
+        assert_eq!(buffer.get_pixel(0, 4), None);
+    }
+
+    fn lit_pixels(buffer: &FrameBuffer, width: u32, height: u32) -> Vec<(u32, u32)> {
+        let mut pixels = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if buffer.get_pixel(x, y) == Some([255, 255, 255, 255]) {
+                    pixels.push((x, y));
+                }
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn draw_line_horizontal() {
+        let mut buffer = FrameBuffer::new(5, 5);
+        buffer.draw_line(0, 2, 4, 2, [255, 255, 255, 255]);
+        assert_eq!(lit_pixels(&buffer, 5, 5), vec![(0, 2), (1, 2), (2, 2), (3, 2), (4, 2)]);
+    }
+
+    #[test]
+    fn draw_line_diagonal() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.draw_line(0, 0, 3, 3, [255, 255, 255, 255]);
+        assert_eq!(lit_pixels(&buffer, 4, 4), vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn draw_line_zero_length_draws_one_pixel() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.draw_line(2, 2, 2, 2, [255, 255, 255, 255]);
+        assert_eq!(lit_pixels(&buffer, 4, 4), vec![(2, 2)]);
+    }
+
+    #[test]
+    fn draw_line_clips_outside_the_buffer() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.draw_line(-2, 0, 5, 0, [255, 255, 255, 255]);
+        assert_eq!(lit_pixels(&buffer, 4, 4), vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn fill_rect_clips_at_each_edge() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.fill_rect(2, 2, 10, 10, [255, 255, 255, 255]);
+        assert_eq!(
+            lit_pixels(&buffer, 4, 4),
+            vec![(2, 2), (3, 2), (2, 3), (3, 3)]
+        );
+    }
+
+    #[test]
+    fn fill_rect_zero_size_is_a_no_op() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.fill_rect(0, 0, 0, 4, [255, 255, 255, 255]);
+        assert!(lit_pixels(&buffer, 4, 4).is_empty());
+    }
+
+    #[test]
+    fn draw_rect_draws_outline_only() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.draw_rect(0, 0, 3, 3, [255, 255, 255, 255]);
+        let mut lit = lit_pixels(&buffer, 4, 4);
+        lit.sort();
+        assert_eq!(
+            lit,
+            vec![
+                (0, 0), (1, 0), (2, 0),
+                (0, 1), (2, 1),
+                (0, 2), (1, 2), (2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn fill_rect_blend_averages_with_existing_pixel() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.set_pixel(0, 0, [0, 0, 0, 255]);
+        buffer.fill_rect_blend(0, 0, 1, 1, [255, 255, 255, 128]);
+        assert_eq!(buffer.get_pixel(0, 0), Some([128, 128, 128, 255]));
+    }
+
+    #[test]
+    fn draw_circle_radius_zero_draws_a_single_pixel() {
+        let mut buffer = FrameBuffer::new(21, 21);
+        buffer.draw_circle(10, 10, 0, [255, 255, 255, 255]);
+        assert_eq!(lit_pixels(&buffer, 21, 21), vec![(10, 10)]);
+    }
+
+    #[test]
+    fn draw_circle_matches_fixture_for_r_1_3_7() {
+        let expected_r1 = vec![(9, 10), (10, 9), (10, 11), (11, 10)];
+        let expected_r3 = vec![
+            (7, 10), (8, 9), (8, 11), (9, 8), (9, 12), (10, 7), (10, 13),
+            (11, 8), (11, 12), (12, 9), (12, 11), (13, 10),
+        ];
+        let expected_r7 = vec![
+            (3, 10), (4, 8), (4, 9), (4, 11), (4, 12), (5, 6), (5, 7), (5, 13), (5, 14),
+            (6, 5), (6, 15), (7, 5), (7, 15), (8, 4), (8, 16), (9, 4), (9, 16),
+            (10, 3), (10, 17), (11, 4), (11, 16), (12, 4), (12, 16), (13, 5), (13, 15),
+            (14, 5), (14, 15), (15, 6), (15, 7), (15, 13), (15, 14),
+            (16, 8), (16, 9), (16, 11), (16, 12), (17, 10),
+        ];
+
+        for (r, mut expected) in [(1, expected_r1), (3, expected_r3), (7, expected_r7)] {
+            let mut buffer = FrameBuffer::new(21, 21);
+            buffer.draw_circle(10, 10, r, [255, 255, 255, 255]);
+            let mut lit = lit_pixels(&buffer, 21, 21);
+            lit.sort();
+            expected.sort();
+            assert_eq!(lit, expected, "mismatch for r = {r}");
+        }
+    }
+
+    #[test]
+    fn draw_circle_clips_outside_the_buffer() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.draw_circle(0, 0, 2, [255, 255, 255, 255]);
+        // Every point with a negative coordinate is clipped; only the
+        // in-bounds quarter of the outline survives.
+        assert!(lit_pixels(&buffer, 4, 4).iter().all(|&(x, y)| x < 4 && y < 4));
+        assert!(!lit_pixels(&buffer, 4, 4).is_empty());
+    }
+
+    fn disk_area(r: i32) -> usize {
+        let mut count = 0;
+        for x in -r..=r {
+            for y in -r..=r {
+                if x * x + y * y <= r * r {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn fill_circle_covers_the_same_area_as_the_analytic_disk() {
+        for r in [1, 3, 7] {
+            let mut buffer = FrameBuffer::new(21, 21);
+            buffer.fill_circle(10, 10, r, [255, 255, 255, 255]);
+            assert_eq!(lit_pixels(&buffer, 21, 21).len(), disk_area(r), "mismatch for r = {r}");
+        }
+    }
+
+    #[test]
+    fn fill_circle_radius_zero_draws_a_single_pixel() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.fill_circle(1, 1, 0, [255, 255, 255, 255]);
+        assert_eq!(lit_pixels(&buffer, 4, 4), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn draw_ellipse_matches_fixture() {
+        let mut expected = vec![
+            (5, 9), (5, 10), (5, 11), (6, 8), (6, 12), (7, 8), (7, 12), (8, 7), (8, 13),
+            (9, 7), (9, 13), (10, 7), (10, 13), (11, 7), (11, 13), (12, 7), (12, 13),
+            (13, 8), (13, 12), (14, 8), (14, 12), (15, 9), (15, 10), (15, 11),
+        ];
+        let mut buffer = FrameBuffer::new(21, 21);
+        buffer.draw_ellipse(10, 10, 5, 3, [255, 255, 255, 255]);
+        let mut lit = lit_pixels(&buffer, 21, 21);
+        lit.sort();
+        expected.sort();
+        assert_eq!(lit, expected);
+    }
+
+    #[test]
+    fn fill_ellipse_covers_the_same_area_as_the_analytic_ellipse() {
+        let (rx, ry) = (5, 3);
+        let mut count = 0;
+        for x in -rx..=rx {
+            for y in -ry..=ry {
+                let fx = x as f64 / rx as f64;
+                let fy = y as f64 / ry as f64;
+                if fx * fx + fy * fy <= 1.0 {
+                    count += 1;
+                }
+            }
+        }
+
+        let mut buffer = FrameBuffer::new(21, 21);
+        buffer.fill_ellipse(10, 10, rx, ry, [255, 255, 255, 255]);
+        assert_eq!(lit_pixels(&buffer, 21, 21).len(), count);
+    }
+
+    // A 2x2 source image with a distinct color per pixel, laid out:
+    //   A B
+    //   C D
+    const BLIT_SRC: [u8; 16] = [
+        1, 1, 1, 255, 2, 2, 2, 255, //
+        3, 3, 3, 255, 4, 4, 4, 255,
+    ];
+
+    #[test]
+    fn blit_copies_fully_inside_the_buffer() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.blit(1, 1, &BLIT_SRC, 2, 2, BlitOptions::default());
+        assert_eq!(buffer.get_pixel(1, 1), Some([1, 1, 1, 255]));
+        assert_eq!(buffer.get_pixel(2, 1), Some([2, 2, 2, 255]));
+        assert_eq!(buffer.get_pixel(1, 2), Some([3, 3, 3, 255]));
+        assert_eq!(buffer.get_pixel(2, 2), Some([4, 4, 4, 255]));
+        assert_eq!(buffer.get_pixel(0, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn blit_clips_the_left_edge() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.blit(-1, 0, &BLIT_SRC, 2, 2, BlitOptions::default());
+        assert_eq!(buffer.get_pixel(0, 0), Some([2, 2, 2, 255]));
+        assert_eq!(buffer.get_pixel(0, 1), Some([4, 4, 4, 255]));
+    }
+
+    #[test]
+    fn blit_clips_the_right_edge() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.blit(1, 0, &BLIT_SRC, 2, 2, BlitOptions::default());
+        assert_eq!(buffer.get_pixel(1, 0), Some([1, 1, 1, 255]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([3, 3, 3, 255]));
+    }
+
+    #[test]
+    fn blit_clips_the_top_edge() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.blit(0, -1, &BLIT_SRC, 2, 2, BlitOptions::default());
+        assert_eq!(buffer.get_pixel(0, 0), Some([3, 3, 3, 255]));
+        assert_eq!(buffer.get_pixel(1, 0), Some([4, 4, 4, 255]));
+    }
+
+    #[test]
+    fn blit_clips_the_bottom_edge() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.blit(0, 1, &BLIT_SRC, 2, 2, BlitOptions::default());
+        assert_eq!(buffer.get_pixel(0, 1), Some([1, 1, 1, 255]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([2, 2, 2, 255]));
+    }
+
+    #[test]
+    fn blit_flip_x_mirrors_the_copied_region() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.blit(
+            0,
+            0,
+            &BLIT_SRC,
+            2,
+            2,
+            BlitOptions { flip_x: true, ..Default::default() },
+        );
+        assert_eq!(buffer.get_pixel(0, 0), Some([2, 2, 2, 255]));
+        assert_eq!(buffer.get_pixel(1, 0), Some([1, 1, 1, 255]));
+        assert_eq!(buffer.get_pixel(0, 1), Some([4, 4, 4, 255]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([3, 3, 3, 255]));
+    }
+
+    #[test]
+    fn blit_flip_y_mirrors_the_copied_region() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.blit(
+            0,
+            0,
+            &BLIT_SRC,
+            2,
+            2,
+            BlitOptions { flip_y: true, ..Default::default() },
+        );
+        assert_eq!(buffer.get_pixel(0, 0), Some([3, 3, 3, 255]));
+        assert_eq!(buffer.get_pixel(1, 0), Some([4, 4, 4, 255]));
+        assert_eq!(buffer.get_pixel(0, 1), Some([1, 1, 1, 255]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([2, 2, 2, 255]));
+    }
+
+    #[test]
+    fn blit_with_src_rect_copies_only_the_sub_rectangle() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.blit(
+            0,
+            0,
+            &BLIT_SRC,
+            2,
+            2,
+            BlitOptions {
+                src_rect: Some((1, 1, 1, 1)),
+                ..Default::default()
+            },
+        );
+        assert_eq!(buffer.get_pixel(0, 0), Some([4, 4, 4, 255]));
+        assert_eq!(buffer.get_pixel(1, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn blit_blend_averages_with_existing_pixel() {
+        let mut buffer = FrameBuffer::new(1, 1);
+        buffer.set_pixel(0, 0, [0, 0, 0, 255]);
+        let src = [255u8, 255, 255, 128];
+        buffer.blit_blend(0, 0, &src, 1, 1, BlitOptions::default());
+        assert_eq!(buffer.get_pixel(0, 0), Some([128, 128, 128, 255]));
+    }
+
+    // A 2x2-per-glyph fixture font sheet, cell 0 = fallback ('?'), cells
+    // 1..3 = 'A', 'B', 'C'. RGB is always 0; only alpha varies, at values
+    // chosen so each glyph and both rows within it are distinguishable.
+    fn fixture_font() -> BitmapFont {
+        let sheet: Vec<u8> = vec![
+            0, 0, 0, 50, 0, 0, 0, 50, 0, 0, 0, 255, 0, 0, 0, 0, //
+            0, 0, 0, 0, 0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255, //
+            0, 0, 0, 50, 0, 0, 0, 50, 0, 0, 0, 0, 0, 0, 0, 255, //
+            0, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, //
+        ];
+        let glyphs = [('A', (1, 0)), ('B', (2, 0)), ('C', (3, 0))].into_iter().collect();
+        BitmapFont::new(sheet, 8, 2, 2, glyphs, (0, 0))
+    }
+
+    #[test]
+    fn draw_text_renders_known_glyphs() {
+        let mut buffer = FrameBuffer::new(6, 2);
+        buffer.draw_text(0, 0, "ABC", &fixture_font(), [9, 9, 9, 255]);
+
+        // 'A' at columns 0..2: alpha [255, 0] over [0, 255].
+        assert_eq!(buffer.get_pixel(0, 0), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(1, 0), Some([0, 0, 0, 0]));
+        assert_eq!(buffer.get_pixel(0, 1), Some([0, 0, 0, 0]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([9, 9, 9, 255]));
+
+        // 'B' at columns 2..4: alpha [0, 255] over [255, 0].
+        assert_eq!(buffer.get_pixel(2, 0), Some([0, 0, 0, 0]));
+        assert_eq!(buffer.get_pixel(3, 0), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(2, 1), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(3, 1), Some([0, 0, 0, 0]));
+
+        // 'C' at columns 4..6: alpha [255, 255] over [0, 0].
+        assert_eq!(buffer.get_pixel(4, 0), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(5, 0), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(4, 1), Some([0, 0, 0, 0]));
+        assert_eq!(buffer.get_pixel(5, 1), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn draw_text_falls_back_for_unmapped_characters() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.draw_text(0, 0, "?", &fixture_font(), [9, 9, 9, 255]);
+        // The fallback glyph's alpha is 50 everywhere, so every pixel in
+        // its cell is src-over blended against the (transparent black)
+        // background at that partial alpha rather than fully overwritten.
+        for y in 0..2 {
+            for x in 0..2 {
+                assert_eq!(buffer.get_pixel(x, y), Some([2, 2, 2, 50]));
+            }
+        }
+    }
+
+    #[test]
+    fn draw_text_newline_advances_to_the_next_line() {
+        let mut buffer = FrameBuffer::new(2, 4);
+        buffer.draw_text(0, 0, "A\nA", &fixture_font(), [9, 9, 9, 255]);
+        assert_eq!(buffer.get_pixel(0, 0), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(0, 2), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(1, 3), Some([9, 9, 9, 255]));
+    }
+
+    #[test]
+    fn draw_text_clips_at_the_right_edge() {
+        let mut buffer = FrameBuffer::new(1, 2);
+        buffer.draw_text(0, 0, "A", &fixture_font(), [9, 9, 9, 255]);
+        assert_eq!(buffer.get_pixel(0, 0), Some([9, 9, 9, 255]));
+        assert_eq!(buffer.get_pixel(0, 1), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn resize_preserves_overlapping_contents_top_left_aligned() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.set_pixel(0, 0, [1, 2, 3, 4]);
+        buffer.set_pixel(1, 1, [5, 6, 7, 8]);
+
+        buffer.resize(3, 3);
+
+        assert_eq!(buffer.get_pixel(0, 0), Some([1, 2, 3, 4]));
+        assert_eq!(buffer.get_pixel(1, 1), Some([5, 6, 7, 8]));
+        assert_eq!(buffer.get_pixel(2, 2), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn resize_smaller_drops_content_outside_the_new_bounds() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        buffer.set_pixel(3, 3, [1, 2, 3, 4]);
+
+        buffer.resize(2, 2);
+
+        assert_eq!(buffer.as_slice().len(), 2 * 2 * 4);
+        assert_eq!(buffer.get_pixel(3, 3), None);
+    }
+
+    #[test]
+    fn resize_marks_the_whole_new_buffer_dirty() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.clear_dirty();
+
+        buffer.resize(4, 4);
+
+        assert_eq!(buffer.dirty_rect(), Some((0, 0, 4, 4)));
+    }
+
+    #[test]
+    fn snapshot_and_restore_round_trip() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        buffer.set_pixel(0, 0, [1, 2, 3, 4]);
+        let snapshot = buffer.snapshot();
+
+        buffer.set_pixel(0, 0, [9, 9, 9, 9]);
+        buffer.restore(&snapshot).unwrap();
+
+        assert_eq!(buffer.get_pixel(0, 0), Some([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn restore_marks_the_whole_buffer_dirty() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        let snapshot = buffer.snapshot();
+        buffer.clear_dirty();
+
+        buffer.restore(&snapshot).unwrap();
+
+        assert_eq!(buffer.dirty_rect(), Some((0, 0, 2, 2)));
+    }
+
+    #[test]
+    fn restore_rejects_a_snapshot_from_before_a_resize() {
+        let mut buffer = FrameBuffer::new(2, 2);
+        let snapshot = buffer.snapshot();
+
+        buffer.resize(4, 4);
+
+        assert!(matches!(
+            buffer.restore(&snapshot),
+            Err(crate::ChromaError::InvalidSnapshotLength { expected: 64, actual: 16 })
+        ));
+    }
+}
@@ -0,0 +1,739 @@
+//! A small, vendored subset of the `pixels` crate's CPU-framebuffer-to-GPU
+//! pipeline, kept in-tree so chroma can customize the upload and scaling
+//! path without waiting on upstream.
+
+mod bitmap_font;
+mod builder;
+#[cfg(feature = "debug-font")]
+mod debug_font_data;
+mod frame_buffer;
+mod renderers;
+mod texture_format_size;
+
+pub(crate) use frame_buffer::FrameBuffer;
+
+pub use bitmap_font::BitmapFont;
+pub use builder::PixelsBuilder;
+pub use frame_buffer::BlitOptions;
+pub use renderers::ScalingRenderer;
+
+/// The dimensions of the window surface `Pixels` is presenting into.
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl SurfaceSize {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    /// Like [`SurfaceSize::new`], but errors with
+    /// [`crate::ChromaError::InvalidSurfaceSize`] instead of constructing a
+    /// zero-area size — a window that's been minimized or not yet mapped
+    /// often reports `0x0`, which would otherwise divide by zero throughout
+    /// the scaling math in [`crate::ScalingMatrix`].
+    pub fn try_new(width: u32, height: u32) -> Result<Self, crate::ChromaError> {
+        if width == 0 || height == 0 {
+            return Err(crate::ChromaError::InvalidSurfaceSize { width, height });
+        }
+        Ok(Self::new(width, height))
+    }
+}
+
+impl From<winit::dpi::PhysicalSize<u32>> for SurfaceSize {
+    fn from(size: winit::dpi::PhysicalSize<u32>) -> Self {
+        Self::new(size.width, size.height)
+    }
+}
+
+/// A wgpu surface plus the window size it was created for.
+pub struct SurfaceTexture<'win> {
+    surface: wgpu::Surface<'win>,
+    size: SurfaceSize,
+}
+
+/// `wgpu::Surface` doesn't implement `Debug`, so this prints a placeholder
+/// for it and the actual `size` alongside.
+impl std::fmt::Debug for SurfaceTexture<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SurfaceTexture")
+            .field("surface", &"<wgpu::Surface>")
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<'win> SurfaceTexture<'win> {
+    pub fn new(surface: wgpu::Surface<'win>, size: SurfaceSize) -> Self {
+        Self { surface, size }
+    }
+
+    /// Creates the `wgpu::Surface` for `window` and pairs it with `width`x
+    /// `height` in one step, for callers who don't already have a
+    /// `wgpu::Surface` lying around. Errors with
+    /// [`crate::ChromaError::SurfaceCreation`] if `instance` can't create a
+    /// surface for `window`, or [`crate::ChromaError::InvalidSurfaceSize`]
+    /// if either dimension is zero.
+    ///
+    /// ```ignore
+    /// let instance = wgpu::Instance::default();
+    /// let surface_texture = SurfaceTexture::from_window(&instance, &window, size.width, size.height)?;
+    /// let pixels = PixelsBuilder::new(320, 240, surface_texture).build()?;
+    /// ```
+    pub fn from_window<W>(
+        instance: &wgpu::Instance,
+        window: &W,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, crate::ChromaError>
+    where
+        W: raw_window_handle::HasRawWindowHandle + raw_window_handle::HasRawDisplayHandle,
+    {
+        let surface = instance.create_surface(window)?;
+        let size = SurfaceSize::try_new(width, height)?;
+        Ok(Self::new(surface, size))
+    }
+}
+
+/// The shared wgpu handles used by the pixel-buffer render path.
+pub struct PixelsContext {
+    pub device: wgpu::Device,
+    pub queue: wgpu::Queue,
+    pub texture: wgpu::Texture,
+    pub texture_format: wgpu::TextureFormat,
+    /// Sample count for a custom render pass drawn alongside the upscaled
+    /// pixel buffer, set via [`crate::pixels::PixelsBuilder::with_msaa`]. `1`
+    /// if MSAA wasn't requested.
+    pub msaa_samples: u32,
+    /// The multisampled color attachment such a pass should resolve into
+    /// the surface, or `None` if `msaa_samples` is `1`.
+    pub msaa_texture: Option<wgpu::Texture>,
+}
+
+/// None of `device`, `queue`, or `texture` implement `Debug`, so this prints
+/// a placeholder for each and the real values for everything else.
+impl std::fmt::Debug for PixelsContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PixelsContext")
+            .field("device", &"<wgpu::Device>")
+            .field("queue", &"<wgpu::Queue>")
+            .field("texture", &"<wgpu::Texture>")
+            .field("texture_format", &self.texture_format)
+            .field("msaa_samples", &self.msaa_samples)
+            .field("msaa_texture", &self.msaa_texture.as_ref().map(|_| "<wgpu::Texture>"))
+            .finish()
+    }
+}
+
+/// A raw CPU-side frame buffer, in [`Pixels::frame_format`]'s channel
+/// order, that [`Pixels::render`] uploads to the GPU each frame.
+pub struct Pixels {
+    context: PixelsContext,
+    surface: wgpu::Surface<'static>,
+    scaling_renderer: ScalingRenderer,
+    surface_size: SurfaceSize,
+    pixel_size: (u32, u32),
+    frame: FrameBuffer,
+    present_mode: wgpu::PresentMode,
+    clear_color: wgpu::Color,
+    blend_state: wgpu::BlendState,
+}
+
+/// `surface` doesn't implement `Debug`; `frame` is reported by its byte
+/// length rather than its pixel contents.
+impl std::fmt::Debug for Pixels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pixels")
+            .field("context", &self.context)
+            .field("surface", &"<wgpu::Surface>")
+            .field("scaling_renderer", &self.scaling_renderer)
+            .field("surface_size", &self.surface_size)
+            .field("pixel_size", &self.pixel_size)
+            .field("frame_bytes", &self.frame.as_slice().len())
+            .field("present_mode", &self.present_mode)
+            .field("clear_color", &self.clear_color)
+            .field("blend_state", &self.blend_state)
+            .finish()
+    }
+}
+
+impl Pixels {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn from_parts(
+        context: PixelsContext,
+        surface: wgpu::Surface<'static>,
+        scaling_renderer: ScalingRenderer,
+        surface_size: SurfaceSize,
+        pixel_size: (u32, u32),
+        present_mode: wgpu::PresentMode,
+        clear_color: wgpu::Color,
+        blend_state: wgpu::BlendState,
+    ) -> Self {
+        let frame = FrameBuffer::new(pixel_size.0, pixel_size.1);
+        Self {
+            context,
+            surface,
+            scaling_renderer,
+            surface_size,
+            pixel_size,
+            frame,
+            present_mode,
+            clear_color,
+            blend_state,
+        }
+    }
+
+    /// The present mode chosen via [`PixelsBuilder::present_mode`].
+    pub fn present_mode(&self) -> wgpu::PresentMode {
+        self.present_mode
+    }
+
+    /// The clear color chosen via [`PixelsBuilder::clear_color`], or the
+    /// last value passed to [`Pixels::set_clear_color`].
+    pub fn clear_color(&self) -> wgpu::Color {
+        self.clear_color
+    }
+
+    /// Changes the color drawn in the letterbox area outside the scaled
+    /// game image, overriding [`PixelsBuilder::clear_color`]. Just an
+    /// assignment, so this is allocation-free and cheap to call every
+    /// frame.
+    pub fn set_clear_color(&mut self, color: wgpu::Color) {
+        self.clear_color = color;
+        self.scaling_renderer.set_clear_color(color);
+    }
+
+    /// The blend state chosen via [`PixelsBuilder::blend_state`].
+    pub fn blend_state(&self) -> wgpu::BlendState {
+        self.blend_state
+    }
+
+    /// The pixel format the CPU frame buffer is laid out in and uploaded as
+    /// — whatever [`PixelsBuilder::texture_format`] was set to. Every color
+    /// passed to [`Pixels::set_pixel`] and friends is interpreted in this
+    /// format's channel order, so callers holding an RGBA color should run
+    /// it through [`rgba_to_frame_format`] first when this isn't
+    /// `Rgba8Unorm`/`Rgba8UnormSrgb` (e.g. a `Bgra8*` surface format).
+    pub fn frame_format(&self) -> wgpu::TextureFormat {
+        self.context.texture_format
+    }
+
+    /// The shared wgpu handles backing this `Pixels`, for integrations
+    /// (egui_wgpu, custom compute) that need to create resources compatible
+    /// with the same device — a texture sampled in a pass registered via
+    /// [`Pixels::render_with`], for instance. `PixelsContext`'s fields are
+    /// themselves `pub`; this and the accessors below are equivalent
+    /// shortcuts to the field a caller actually wants.
+    ///
+    /// Callers must not change the returned texture's format or drop its
+    /// `TEXTURE_BINDING | COPY_DST | COPY_SRC` usage flags —
+    /// [`Pixels::render`] and [`Pixels::read_texture`] assume all three when
+    /// they upload the frame buffer, bind the texture for the upscale pass,
+    /// and copy it back out.
+    pub fn context(&self) -> &PixelsContext {
+        &self.context
+    }
+
+    /// The wgpu device this `Pixels` was built with. See [`Pixels::context`].
+    pub fn device(&self) -> &wgpu::Device {
+        &self.context.device
+    }
+
+    /// The wgpu queue this `Pixels` was built with. See [`Pixels::context`].
+    pub fn queue(&self) -> &wgpu::Queue {
+        &self.context.queue
+    }
+
+    /// The backing pixel texture, in [`Pixels::frame_format`]. Must not be
+    /// replaced or resized directly — use [`Pixels::resize_buffer`], which
+    /// keeps [`Pixels::texture_extent`] and the scaling matrix in sync with
+    /// it.
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.context.texture
+    }
+
+    /// The `wgpu::Extent3d` of the backing pixel texture, for creating
+    /// companion textures or views sized to match it.
+    pub fn texture_extent(&self) -> wgpu::Extent3d {
+        wgpu::Extent3d {
+            width: self.pixel_size.0,
+            height: self.pixel_size.1,
+            depth_or_array_layers: 1,
+        }
+    }
+
+    /// The renderer that upscales the pixel texture onto the window surface,
+    /// for reading its current scale factor and clip rect. See
+    /// [`ScalingRenderer::clip_rect`].
+    pub fn scaling_renderer(&self) -> &ScalingRenderer {
+        &self.scaling_renderer
+    }
+
+    /// Reconfigures the surface for a new window size, e.g. after a
+    /// `WindowEvent::Resized`. Recomputes the scaling matrix and clip rect
+    /// for the new surface size, but leaves the pixel buffer's logical
+    /// resolution untouched; use [`Pixels::resize_buffer`] to change that.
+    pub fn resize_surface(&mut self, width: u32, height: u32) {
+        self.surface_size = SurfaceSize::new(width, height);
+        self.surface.configure(
+            &self.context.device,
+            &wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: self.context.texture_format,
+                width,
+                height,
+                present_mode: self.present_mode,
+                alpha_mode: wgpu::CompositeAlphaMode::Auto,
+                view_formats: vec![],
+                desired_maximum_frame_latency: 2,
+            },
+        );
+        self.scaling_renderer
+            .update_surface_size(&self.context.queue, &self.surface_size);
+    }
+
+    /// Changes the pixel buffer's logical resolution, recreating the
+    /// backing texture and reallocating the CPU frame buffer. Old contents
+    /// are preserved top-left aligned where the old and new sizes overlap.
+    /// Recomputes the scaling matrix and clip rect against the current
+    /// surface size; use [`Pixels::resize_surface`] first if that changed
+    /// too.
+    pub fn resize_buffer(&mut self, width: u32, height: u32) {
+        self.pixel_size = (width, height);
+        self.frame.resize(width, height);
+
+        self.context.texture = self.context.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("chroma-pixels-texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.context.texture_format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        self.scaling_renderer.resize(
+            self.pixel_size,
+            (self.surface_size.width, self.surface_size.height),
+        );
+    }
+
+    /// Maps a window-space position (e.g. from a winit cursor event) to a
+    /// pixel-buffer coordinate. `Ok` if `pos` falls inside the scaled
+    /// image; otherwise `Err` with the coordinate clamped to the buffer's
+    /// bounds. Delegates to the scaling matrix `resize_surface` and
+    /// `resize_buffer` keep up to date, so it stays correct across either.
+    pub fn window_pos_to_pixel(&self, pos: (f32, f32)) -> Result<(u32, u32), (i32, i32)> {
+        self.scaling_renderer.window_pos_to_pixel(pos, self.pixel_size)
+    }
+
+    /// Like [`Pixels::window_pos_to_pixel`], but always returns a
+    /// coordinate, clamped to the pixel buffer's bounds if `pos` fell
+    /// outside the scaled image.
+    pub fn clamp_pixel_pos(&self, pos: (f32, f32)) -> (u32, u32) {
+        match self.window_pos_to_pixel(pos) {
+            Ok(pixel) => pixel,
+            Err((x, y)) => (x as u32, y as u32),
+        }
+    }
+
+    /// The mutable CPU-side frame buffer, laid out as 8-bit-per-channel rows
+    /// in [`Pixels::frame_format`]'s channel order, with `(0, 0)` at the
+    /// top-left.
+    pub fn frame_mut(&mut self) -> &mut [u8] {
+        self.frame.as_mut_slice()
+    }
+
+    pub fn frame(&self) -> &[u8] {
+        self.frame.as_slice()
+    }
+
+    /// Clones the current frame buffer, in [`Pixels::frame_format`]'s
+    /// channel order, for later [`Pixels::restore`] — e.g. to implement undo
+    /// in a drawing tool, or to diff frames for dirty detection.
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.frame.snapshot()
+    }
+
+    /// Overwrites the frame buffer with a previously taken
+    /// [`Pixels::snapshot`]. Errors with
+    /// [`crate::ChromaError::InvalidSnapshotLength`] instead of copying if
+    /// `data`'s length doesn't match the current buffer size — the snapshot
+    /// must have been taken before any intervening [`Pixels::resize_buffer`]
+    /// call changed it.
+    pub fn restore(&mut self, data: &[u8]) -> Result<(), crate::ChromaError> {
+        self.frame.restore(data)
+    }
+
+    /// Sets the color of the pixel at `(x, y)`, with `(0, 0)` at the
+    /// top-left. `color`'s channels must already be in
+    /// [`Pixels::frame_format`]'s order — see [`rgba_to_frame_format`] if
+    /// you're holding an RGBA color. A documented no-op if `(x, y)` is out
+    /// of bounds.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        self.frame.set_pixel(x, y, color);
+    }
+
+    /// Returns the color of the pixel at `(x, y)` in [`Pixels::frame_format`]'s
+    /// channel order, or `None` if out of bounds.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        self.frame.get_pixel(x, y)
+    }
+
+    /// Like [`Pixels::set_pixel`], but skips the bounds check. Callers must
+    /// guarantee `x < width` and `y < height`.
+    pub fn set_pixel_unchecked(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        self.frame.set_pixel_unchecked(x, y, color);
+    }
+
+    /// Draws a line from `(x0, y0)` to `(x1, y1)` with Bresenham's
+    /// algorithm. Coordinates may fall outside the buffer; the portion that
+    /// does is clipped rather than panicking.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: [u8; 4]) {
+        self.frame.draw_line(x0, y0, x1, y1, color);
+    }
+
+    /// Mutable access to row `y` of the frame buffer, as `width * 4` bytes
+    /// in [`Pixels::frame_format`]'s channel order. `None` if `y` is out of
+    /// bounds. For software renderers (raycasters, per-scanline effects)
+    /// that want to write a whole row at once instead of looping
+    /// [`Pixels::set_pixel`]. Stays correct across [`Pixels::resize_buffer`]
+    /// since the stride is recomputed from the current dimensions each call.
+    pub fn row_mut(&mut self, y: u32) -> Option<&mut [u8]> {
+        self.frame.row_mut(y)
+    }
+
+    /// Mutable access to every row of the frame buffer in top-to-bottom
+    /// order, each as `width * 4` bytes in [`Pixels::frame_format`]'s
+    /// channel order. See [`Pixels::row_mut`].
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        self.frame.rows_mut()
+    }
+
+    /// Overwrites every pixel in the frame buffer with `color`. Faster than
+    /// looping [`Pixels::set_pixel`] over the whole buffer.
+    pub fn fill(&mut self, color: [u8; 4]) {
+        self.frame.fill(color);
+    }
+
+    /// Fills the rectangle `(x, y, w, h)` with `color`, clipped to the
+    /// buffer bounds.
+    pub fn fill_rect(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 4]) {
+        self.frame.fill_rect(x, y, w, h, color);
+    }
+
+    /// Draws the 1px outline of the rectangle `(x, y, w, h)`, clipped to
+    /// the buffer bounds.
+    pub fn draw_rect(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 4]) {
+        self.frame.draw_rect(x, y, w, h, color);
+    }
+
+    /// Like [`Pixels::fill_rect`], but src-over alpha-blends `color`
+    /// against the existing pixels. Useful for HUD bars and dimming
+    /// overlays.
+    pub fn fill_rect_blend(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 4]) {
+        self.frame.fill_rect_blend(x, y, w, h, color);
+    }
+
+    /// Draws the outline of a circle centered at `(cx, cy)` with radius `r`,
+    /// clipped to the buffer bounds. Radius 0 draws a single pixel.
+    pub fn draw_circle(&mut self, cx: i32, cy: i32, r: i32, color: [u8; 4]) {
+        self.frame.draw_circle(cx, cy, r, color);
+    }
+
+    /// Fills a circle centered at `(cx, cy)` with radius `r`, clipped to the
+    /// buffer bounds. Radius 0 draws a single pixel.
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, r: i32, color: [u8; 4]) {
+        self.frame.fill_circle(cx, cy, r, color);
+    }
+
+    /// Draws the outline of an ellipse centered at `(cx, cy)` with radii
+    /// `(rx, ry)`, clipped to the buffer bounds.
+    pub fn draw_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: [u8; 4]) {
+        self.frame.draw_ellipse(cx, cy, rx, ry, color);
+    }
+
+    /// Fills an ellipse centered at `(cx, cy)` with radii `(rx, ry)`,
+    /// clipped to the buffer bounds.
+    pub fn fill_ellipse(&mut self, cx: i32, cy: i32, rx: i32, ry: i32, color: [u8; 4]) {
+        self.frame.fill_ellipse(cx, cy, rx, ry, color);
+    }
+
+    /// Copies `src`, an image `src_w` by `src_h` already in
+    /// [`Pixels::frame_format`]'s channel order, into the frame buffer at
+    /// `(x, y)`, clipping any rows/columns that fall outside it. `x`/`y` may
+    /// be negative.
+    pub fn blit(&mut self, x: i32, y: i32, src: &[u8], src_w: u32, src_h: u32, options: BlitOptions) {
+        self.frame.blit(x, y, src, src_w, src_h, options);
+    }
+
+    /// Like [`Pixels::blit`], but src-over alpha-blends each copied pixel
+    /// against the existing one instead of overwriting it.
+    pub fn blit_blend(&mut self, x: i32, y: i32, src: &[u8], src_w: u32, src_h: u32, options: BlitOptions) {
+        self.frame.blit_blend(x, y, src, src_w, src_h, options);
+    }
+
+    /// Draws `text` starting at `(x, y)` using `font`, tinted with `color`.
+    /// Newlines advance by the font's glyph height; text running off the
+    /// right or bottom edge of the frame buffer clips.
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, font: &BitmapFont, color: [u8; 4]) {
+        self.frame.draw_text(x, y, text, font, color);
+    }
+
+    /// Uploads the dirty part of the CPU frame buffer, acquires the next
+    /// surface texture (retrying once after reconfiguring on
+    /// `SurfaceError::Lost`), and runs the scaling pass, returning the
+    /// pieces [`Pixels::render`] and [`Pixels::render_with`] need to finish
+    /// the frame.
+    ///
+    /// `wgpu::Queue::write_texture` pads each row internally to
+    /// `COPY_BYTES_PER_ROW_ALIGNMENT`, but it still wants to know the
+    /// *unpadded* stride of the frame buffer — that's `width` times the
+    /// configured texture format's bytes per pixel, regardless of whether
+    /// `width` is itself a multiple of the alignment. That stride describes
+    /// the full buffer even for a partial upload: `offset` seeks to the
+    /// dirty rect's first row and `extent` limits how much of each row
+    /// after it is read.
+    fn begin_frame(
+        &mut self,
+    ) -> Result<(wgpu::CommandEncoder, wgpu::SurfaceTexture, wgpu::TextureView), wgpu::SurfaceError>
+    {
+        let stride = unpadded_bytes_per_row(self.pixel_size.0, self.context.texture_format);
+        if let Some((x0, y0, x1, y1)) = self.frame.dirty_rect() {
+            let (_, _, bytes_per_block) =
+                texture_format_size::texture_format_block_size(self.context.texture_format)
+                    .expect("chroma only uses pixel formats with a known block size");
+            let offset = (y0 as u64) * (stride as u64) + (x0 as u64) * (bytes_per_block as u64);
+            self.context.queue.write_texture(
+                wgpu::ImageCopyTexture {
+                    origin: wgpu::Origin3d { x: x0, y: y0, z: 0 },
+                    ..self.context.texture.as_image_copy()
+                },
+                self.frame.as_slice(),
+                wgpu::ImageDataLayout {
+                    offset,
+                    bytes_per_row: Some(stride),
+                    rows_per_image: Some(y1 - y0),
+                },
+                wgpu::Extent3d {
+                    width: x1 - x0,
+                    height: y1 - y0,
+                    depth_or_array_layers: 1,
+                },
+            );
+            self.frame.clear_dirty();
+        }
+
+        let surface_texture = match self.surface.get_current_texture() {
+            Ok(texture) => texture,
+            Err(wgpu::SurfaceError::Lost) => {
+                self.resize_surface(self.surface_size.width, self.surface_size.height);
+                self.surface.get_current_texture()?
+            }
+            Err(err) => return Err(err),
+        };
+        let view = surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let encoder = self
+            .context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("chroma-pixels-encoder"),
+            });
+
+        Ok((encoder, surface_texture, view))
+    }
+
+    /// Uploads the frame buffer to the backing texture, runs the scaling
+    /// pass and presents the surface. A no-op upload (but not a no-op
+    /// render) if nothing was drawn since the last call. The scaling pass
+    /// records no draw commands (see [`ScalingRenderer`]), so the presented
+    /// surface does not show the pixel buffer.
+    pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
+        let (mut encoder, surface_texture, view) = self.begin_frame()?;
+
+        self.scaling_renderer.render(&mut encoder, &view);
+
+        self.context.queue.submit(std::iter::once(encoder.finish()));
+        surface_texture.present();
+
+        Ok(())
+    }
+
+    /// Like [`Pixels::render`], but runs `f` with the command encoder,
+    /// surface view, and GPU context after the scaling pass and before the
+    /// frame is submitted — for a custom render pass drawn over the
+    /// upscaled pixel buffer (a HUD, particles, 3D elements).
+    pub fn render_with(
+        &mut self,
+        f: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::TextureView, &PixelsContext),
+    ) -> Result<(), wgpu::SurfaceError> {
+        let (mut encoder, surface_texture, view) = self.begin_frame()?;
+
+        self.scaling_renderer.render(&mut encoder, &view);
+        f(&mut encoder, &view, &self.context);
+
+        self.context.queue.submit(std::iter::once(encoder.finish()));
+        surface_texture.present();
+
+        Ok(())
+    }
+
+    /// Copies the backing pixel texture back to the CPU as tightly packed
+    /// [`Pixels::frame_format`] bytes, stripping wgpu's
+    /// `COPY_BYTES_PER_ROW_ALIGNMENT` row padding — for golden-image tests
+    /// or an "export the current frame" feature. Blocks the calling thread
+    /// on the buffer map; unavailable on `wasm32`, where blocking isn't
+    /// allowed — use [`Pixels::read_texture_async`] there instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn read_texture(&self) -> Result<Vec<u8>, crate::ChromaError> {
+        pollster::block_on(self.read_texture_async())
+    }
+
+    /// Like [`Pixels::read_texture`], but `await`s the buffer map instead of
+    /// blocking. The only readback path available on `wasm32`.
+    pub async fn read_texture_async(&self) -> Result<Vec<u8>, crate::ChromaError> {
+        let (width, height) = self.pixel_size;
+        let (_, _, bytes_per_block) =
+            texture_format_size::texture_format_block_size(self.context.texture_format)
+                .expect("chroma only uses pixel formats with a known block size");
+
+        let unpadded_bytes_per_row = width * bytes_per_block;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = unpadded_bytes_per_row.div_ceil(align) * align;
+
+        let buffer = self.context.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("chroma-pixels-readback-buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .context
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("chroma-pixels-readback-encoder"),
+            });
+        encoder.copy_texture_to_buffer(
+            self.context.texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.context.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let result = std::rc::Rc::new(std::cell::RefCell::new(None));
+        let result_for_callback = result.clone();
+        slice.map_async(wgpu::MapMode::Read, move |map_result| {
+            *result_for_callback.borrow_mut() = Some(map_result);
+        });
+        MapFuture {
+            result: result.clone(),
+            device: &self.context.device,
+        }
+        .await
+        .map_err(|err| crate::ChromaError::BufferMap(err.to_string()))?;
+
+        let padded_data = slice.get_mapped_range();
+        let mut tightly_packed = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        for row in padded_data.chunks(padded_bytes_per_row as usize) {
+            tightly_packed.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+        drop(padded_data);
+        buffer.unmap();
+
+        Ok(tightly_packed)
+    }
+}
+
+/// Resolves once `wgpu::BufferSlice::map_async`'s callback has stored a
+/// result into `result`, driving completion by polling `device`
+/// non-blockingly (`wgpu::Maintain::Poll`) on every poll of this future
+/// itself instead of pulling in a channel crate — the same pattern wgpu's
+/// own examples use to stay portable between native (driven by
+/// [`pollster::block_on`]) and `wasm32` (driven by
+/// `wasm_bindgen_futures::spawn_local`), where nothing else pumps the
+/// device.
+struct MapFuture<'a> {
+    result: std::rc::Rc<std::cell::RefCell<Option<Result<(), wgpu::BufferAsyncError>>>>,
+    device: &'a wgpu::Device,
+}
+
+impl<'a> std::future::Future for MapFuture<'a> {
+    type Output = Result<(), wgpu::BufferAsyncError>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        self.device.poll(wgpu::Maintain::Poll);
+        match self.result.borrow_mut().take() {
+            Some(result) => std::task::Poll::Ready(result),
+            None => {
+                cx.waker().wake_by_ref();
+                std::task::Poll::Pending
+            }
+        }
+    }
+}
+
+/// The unpadded row stride, in bytes, of a `width`-pixel-wide row of
+/// `format`.
+fn unpadded_bytes_per_row(width: u32, format: wgpu::TextureFormat) -> u32 {
+    texture_format_size::unpadded_bytes_per_row(format, width)
+        .expect("chroma only uses pixel formats with a known block size")
+}
+
+/// Converts an RGBA color to `format`'s channel order, for passing to
+/// [`Pixels::set_pixel`] and friends when [`Pixels::frame_format`] isn't
+/// plain RGBA (currently that just means a `Bgra8*` texture format, which
+/// swaps red and blue). Since alpha never moves, this function is its own
+/// inverse — the same call converts a frame-format color read back out of
+/// the buffer to RGBA.
+pub fn rgba_to_frame_format(color: [u8; 4], format: wgpu::TextureFormat) -> [u8; 4] {
+    texture_format_size::rgba_to_frame_format(color, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpadded_bytes_per_row_matches_frame_stride() {
+        // The frame buffer is tightly packed, so the row stride handed to
+        // `write_texture` should never itself be padded, even for widths
+        // that aren't multiples of 64.
+        assert_eq!(unpadded_bytes_per_row(1, wgpu::TextureFormat::Rgba8UnormSrgb), 4);
+        assert_eq!(unpadded_bytes_per_row(17, wgpu::TextureFormat::Rgba8UnormSrgb), 68);
+        assert_eq!(unpadded_bytes_per_row(320, wgpu::TextureFormat::Rgba8UnormSrgb), 1280);
+    }
+
+    #[test]
+    fn unpadded_bytes_per_row_respects_the_texture_format() {
+        // A format with a different bytes-per-pixel than chroma's default
+        // should scale the stride accordingly rather than assuming 4.
+        assert_eq!(unpadded_bytes_per_row(4, wgpu::TextureFormat::R8Unorm), 4);
+        assert_eq!(unpadded_bytes_per_row(4, wgpu::TextureFormat::Rg8Unorm), 8);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::pixels::SurfaceSize;
+use crate::scaling_matrix::ScalingMatrix;
+
+/// Holds the scaling matrix and clip rect for upscaling the small
+/// pixel-buffer texture onto the (larger) surface. No render pipeline or
+/// bind group is built, so `render()` records no draw commands and the
+/// surface never shows the pixel texture. Read-only accessors are
+/// `pub` (via [`super::Pixels::scaling_renderer`]) for wgpu interop that
+/// needs to know the current scale/clip rect; everything that mutates it
+/// stays `pub(crate)`, driven only by [`super::Pixels`] itself.
+pub struct ScalingRenderer {
+    render_pipeline: Option<wgpu::RenderPipeline>,
+    bind_group: Option<wgpu::BindGroup>,
+    matrix: ScalingMatrix,
+    texture_size: (u32, u32),
+    texture_format: wgpu::TextureFormat,
+    clear_color: wgpu::Color,
+    blend_state: wgpu::BlendState,
+}
+
+/// `render_pipeline` and `bind_group` don't implement `Debug`, so this
+/// reports only whether each has been built yet.
+impl std::fmt::Debug for ScalingRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScalingRenderer")
+            .field("render_pipeline", &self.render_pipeline.as_ref().map(|_| "<wgpu::RenderPipeline>"))
+            .field("bind_group", &self.bind_group.as_ref().map(|_| "<wgpu::BindGroup>"))
+            .field("matrix", &self.matrix)
+            .field("texture_size", &self.texture_size)
+            .field("texture_format", &self.texture_format)
+            .field("clear_color", &self.clear_color)
+            .field("blend_state", &self.blend_state)
+            .finish()
+    }
+}
+
+impl ScalingRenderer {
+    pub(crate) fn new(
+        _device: &wgpu::Device,
+        _texture_view: &wgpu::TextureView,
+        texture_size: (u32, u32),
+        surface_size: (u32, u32),
+        texture_format: wgpu::TextureFormat,
+        clear_color: wgpu::Color,
+        blend_state: wgpu::BlendState,
+    ) -> Self {
+        let matrix = ScalingMatrix::new(texture_size, surface_size);
+
+        Self {
+            render_pipeline: None,
+            bind_group: None,
+            matrix,
+            texture_size,
+            texture_format,
+            clear_color,
+            blend_state,
+        }
+    }
+
+    pub(crate) fn resize(&mut self, texture_size: (u32, u32), surface_size: (u32, u32)) {
+        self.texture_size = texture_size;
+        self.matrix = ScalingMatrix::new(texture_size, surface_size);
+    }
+
+    /// Recomputes the scaling matrix for a new surface size, keeping the
+    /// current pixel-buffer size (use [`ScalingRenderer::resize`] instead if
+    /// that changed too, e.g. via [`super::Pixels::resize_buffer`]). The new
+    /// clip rect is picked up automatically by [`ScalingRenderer::clip_rect`],
+    /// which reads it straight off the recomputed matrix rather than caching
+    /// it separately.
+    ///
+    /// `queue` is unused today — the transform is only ever read back via
+    /// [`ScalingRenderer::window_pos_to_pixel`] and applied by `render()`'s
+    /// stubbed pipeline, so there's no uniform buffer yet to re-upload it
+    /// to. It's part of the signature so this doesn't need to change again
+    /// once that pipeline exists.
+    pub(crate) fn update_surface_size(&mut self, _queue: &wgpu::Queue, surface_size: &SurfaceSize) {
+        self.resize(self.texture_size, (surface_size.width, surface_size.height));
+    }
+
+    /// Sets the color `render()`'s `LoadOp::Clear` uses for the letterbox
+    /// area. Just an assignment, so changing it every frame is
+    /// allocation-free.
+    pub(crate) fn set_clear_color(&mut self, color: wgpu::Color) {
+        self.clear_color = color;
+    }
+
+    pub(crate) fn render(
+        &self,
+        _encoder: &mut wgpu::CommandEncoder,
+        _target: &wgpu::TextureView,
+    ) {
+        // Pipeline construction is stubbed out in this vendored subset; the
+        // scaling matrix and clip rect below are what downstream code
+        // actually depends on today. `texture_format`/`clear_color`/
+        // `blend_state` are held here for when the pipeline and render pass
+        // are built, matching what the color target and load op will need.
+        let _ = (
+            &self.render_pipeline,
+            &self.bind_group,
+            self.texture_format,
+            self.clear_color,
+            self.blend_state,
+        );
+    }
+
+    pub fn clip_rect(&self) -> (u32, u32, u32, u32) {
+        self.matrix.clip_rect()
+    }
+
+    pub fn window_pos_to_pixel(
+        &self,
+        pos: (f32, f32),
+        pixel_size: (u32, u32),
+    ) -> Result<(u32, u32), (i32, i32)> {
+        self.matrix.window_pos_to_pixel(pos, pixel_size)
+    }
+}
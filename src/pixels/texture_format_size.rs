@@ -0,0 +1,163 @@
+/// The dimensions of a single compression block for `format`, in pixels,
+/// and the block's size in bytes: `(block_width, block_height,
+/// bytes_per_block)`. Uncompressed formats have a 1x1 "block" (i.e. a
+/// pixel). `None` if `format` isn't one this module knows the size of,
+/// rather than silently guessing.
+pub(crate) fn texture_format_block_size(format: wgpu::TextureFormat) -> Option<(u32, u32, u32)> {
+    use wgpu::{AstcBlock, AstcChannel, TextureFormat::*};
+
+    match format {
+        R8Unorm | R8Snorm | R8Uint | R8Sint => Some((1, 1, 1)),
+        Rg8Unorm | Rg8Snorm | Rg8Uint | Rg8Sint => Some((1, 1, 2)),
+        Rgba8Unorm | Rgba8UnormSrgb | Rgba8Snorm | Rgba8Uint | Rgba8Sint | Bgra8Unorm
+        | Bgra8UnormSrgb => Some((1, 1, 4)),
+        R16Unorm | R16Snorm | R16Uint | R16Sint | R16Float => Some((1, 1, 2)),
+        Rg16Unorm | Rg16Snorm | Rg16Uint | Rg16Sint | Rg16Float => Some((1, 1, 4)),
+        Rgba16Unorm | Rgba16Snorm | Rgba16Uint | Rgba16Sint | Rgba16Float => Some((1, 1, 8)),
+        R32Uint | R32Sint | R32Float => Some((1, 1, 4)),
+        Rg32Uint | Rg32Sint | Rg32Float => Some((1, 1, 8)),
+        Rgba32Uint | Rgba32Sint | Rgba32Float => Some((1, 1, 16)),
+        Bc1RgbaUnorm | Bc1RgbaUnormSrgb | Bc4RUnorm | Bc4RSnorm => Some((4, 4, 8)),
+        Bc2RgbaUnorm | Bc2RgbaUnormSrgb | Bc3RgbaUnorm | Bc3RgbaUnormSrgb | Bc5RgUnorm
+        | Bc5RgSnorm | Bc6hRgbUfloat | Bc6hRgbFloat | Bc7RgbaUnorm | Bc7RgbaUnormSrgb => {
+            Some((4, 4, 16))
+        }
+        // ASTC always packs a block into 128 bits, whatever the block's
+        // pixel dimensions.
+        Astc { block, channel } => {
+            let _: AstcChannel = channel;
+            let (width, height) = match block {
+                AstcBlock::B4x4 => (4, 4),
+                AstcBlock::B5x4 => (5, 4),
+                AstcBlock::B5x5 => (5, 5),
+                AstcBlock::B6x5 => (6, 5),
+                AstcBlock::B6x6 => (6, 6),
+                AstcBlock::B8x5 => (8, 5),
+                AstcBlock::B8x6 => (8, 6),
+                AstcBlock::B8x8 => (8, 8),
+                AstcBlock::B10x5 => (10, 5),
+                AstcBlock::B10x6 => (10, 6),
+                AstcBlock::B10x8 => (10, 8),
+                AstcBlock::B10x10 => (10, 10),
+                AstcBlock::B12x10 => (12, 10),
+                AstcBlock::B12x12 => (12, 12),
+            };
+            Some((width, height, 16))
+        }
+        _ => None,
+    }
+}
+
+/// The size, in bytes, of a `width`x`height` image in `format`, accounting
+/// for block-compressed formats where a partial block along an edge still
+/// occupies a whole block. `None` if `format`'s block size isn't known.
+pub(crate) fn pixels_buffer_size(format: wgpu::TextureFormat, width: u32, height: u32) -> Option<usize> {
+    let (block_width, block_height, bytes_per_block) = texture_format_block_size(format)?;
+    let blocks_x = (width + block_width - 1) / block_width;
+    let blocks_y = (height + block_height - 1) / block_height;
+    Some(blocks_x as usize * blocks_y as usize * bytes_per_block as usize)
+}
+
+/// The unpadded byte stride of a single row of blocks covering `width`
+/// pixels in `format`, for `wgpu::ImageDataLayout::bytes_per_row`. `None`
+/// if `format`'s block size isn't known.
+pub(crate) fn unpadded_bytes_per_row(format: wgpu::TextureFormat, width: u32) -> Option<u32> {
+    let (block_width, _, bytes_per_block) = texture_format_block_size(format)?;
+    let blocks_x = (width + block_width - 1) / block_width;
+    Some(blocks_x * bytes_per_block)
+}
+
+/// Swaps the R and B channels of `color` if `format` stores pixels in BGRA
+/// order, leaving it untouched otherwise. Alpha always stays in the last
+/// slot in either order, so swapping R and B is the only change needed —
+/// which also makes this function its own inverse: converting a color read
+/// out of a BGRA-ordered buffer back to RGBA is the same call.
+pub(crate) fn rgba_to_frame_format(color: [u8; 4], format: wgpu::TextureFormat) -> [u8; 4] {
+    if is_bgra(format) {
+        [color[2], color[1], color[0], color[3]]
+    } else {
+        color
+    }
+}
+
+fn is_bgra(format: wgpu::TextureFormat) -> bool {
+    matches!(
+        format,
+        wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wgpu::TextureFormat;
+
+    #[test]
+    fn rgba8_is_one_pixel_blocks_of_four_bytes() {
+        assert_eq!(
+            texture_format_block_size(TextureFormat::Rgba8UnormSrgb),
+            Some((1, 1, 4))
+        );
+        assert_eq!(pixels_buffer_size(TextureFormat::Rgba8UnormSrgb, 17, 9), Some(17 * 9 * 4));
+        assert_eq!(unpadded_bytes_per_row(TextureFormat::Rgba8UnormSrgb, 17), Some(68));
+    }
+
+    #[test]
+    fn rg32float_is_one_pixel_blocks_of_eight_bytes() {
+        assert_eq!(
+            texture_format_block_size(TextureFormat::Rg32Float),
+            Some((1, 1, 8))
+        );
+        assert_eq!(pixels_buffer_size(TextureFormat::Rg32Float, 4, 4), Some(4 * 4 * 8));
+    }
+
+    #[test]
+    fn bc1_is_four_by_four_blocks_of_eight_bytes_rounded_up() {
+        assert_eq!(
+            texture_format_block_size(TextureFormat::Bc1RgbaUnorm),
+            Some((4, 4, 8))
+        );
+        // 6x6 pixels needs 2x2 blocks (partial blocks still count whole).
+        assert_eq!(pixels_buffer_size(TextureFormat::Bc1RgbaUnorm, 6, 6), Some(2 * 2 * 8));
+        assert_eq!(unpadded_bytes_per_row(TextureFormat::Bc1RgbaUnorm, 6), Some(2 * 8));
+    }
+
+    #[test]
+    fn astc_variant_is_16_bytes_per_block_regardless_of_block_size() {
+        let format = TextureFormat::Astc {
+            block: wgpu::AstcBlock::B8x8,
+            channel: wgpu::AstcChannel::UnormSrgb,
+        };
+        assert_eq!(texture_format_block_size(format), Some((8, 8, 16)));
+        // 20x20 pixels needs ceil(20/8) = 3 blocks per axis.
+        assert_eq!(pixels_buffer_size(format, 20, 20), Some(3 * 3 * 16));
+    }
+
+    #[test]
+    fn unknown_format_returns_none_instead_of_guessing() {
+        assert_eq!(texture_format_block_size(TextureFormat::Depth32Float), None);
+        assert_eq!(pixels_buffer_size(TextureFormat::Depth32Float, 4, 4), None);
+    }
+
+    #[test]
+    fn rgba_format_leaves_color_untouched() {
+        let color = [10, 20, 30, 40];
+        assert_eq!(rgba_to_frame_format(color, TextureFormat::Rgba8UnormSrgb), color);
+    }
+
+    #[test]
+    fn bgra_format_swaps_red_and_blue() {
+        let rgba = [10, 20, 30, 40];
+        let bgra = rgba_to_frame_format(rgba, TextureFormat::Bgra8UnormSrgb);
+        assert_eq!(bgra, [30, 20, 10, 40]);
+    }
+
+    #[test]
+    fn conversion_round_trips_for_both_orders() {
+        let original = [10, 20, 30, 40];
+        for format in [TextureFormat::Rgba8UnormSrgb, TextureFormat::Bgra8Unorm] {
+            let converted = rgba_to_frame_format(original, format);
+            assert_eq!(rgba_to_frame_format(converted, format), original);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+/// Picks `Fifo` (VSync, no tearing) if the surface supports it, falling
+/// back to whatever the driver reports first otherwise.
+///
+/// `surface_capabilities.present_modes[0]` is not guaranteed to be `Fifo` —
+/// on some Vulkan drivers it's `Immediate`, which tears by default unless
+/// callers opt out explicitly.
+pub(crate) fn choose_present_mode(supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+    if supported.contains(&wgpu::PresentMode::Fifo) {
+        wgpu::PresentMode::Fifo
+    } else {
+        supported[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_fifo_when_available() {
+        let supported = [wgpu::PresentMode::Immediate, wgpu::PresentMode::Fifo];
+        assert_eq!(choose_present_mode(&supported), wgpu::PresentMode::Fifo);
+    }
+
+    #[test]
+    fn falls_back_to_first_when_fifo_unsupported() {
+        let supported = [wgpu::PresentMode::Immediate, wgpu::PresentMode::Mailbox];
+        assert_eq!(choose_present_mode(&supported), wgpu::PresentMode::Immediate);
+    }
+}
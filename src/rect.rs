@@ -0,0 +1,18 @@
+/// An axis-aligned rectangle, used for bounds checks such as
+/// [`crate::Chroma::virtual_rect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    pub fn contains(&self, point: cgmath::Vector2<f32>) -> bool {
+        point.x >= self.x as f32
+            && point.x < (self.x + self.width) as f32
+            && point.y >= self.y as f32
+            && point.y < (self.y + self.height) as f32
+    }
+}
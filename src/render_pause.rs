@@ -0,0 +1,77 @@
+/// Whether GPU work should be paused because the window is minimized
+/// (reported as a zero-size `WindowEvent::Resized`) or occluded — tracked as
+/// two independent flags since either can flip without the other changing.
+/// Driven by [`crate::Chroma::resize`] and [`crate::Chroma::set_occluded`];
+/// read by [`crate::Chroma::render`] to skip rendering while [`is_paused`]
+/// is `true`, and by [`crate::Chroma::run`] to switch the event loop to
+/// `ControlFlow::Wait` while paused instead of busy-polling.
+///
+/// [`is_paused`]: RenderPause::is_paused
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RenderPause {
+    minimized: bool,
+    occluded: bool,
+}
+
+impl RenderPause {
+    pub(crate) fn on_resize(&mut self, width: u32, height: u32) {
+        self.minimized = width == 0 || height == 0;
+    }
+
+    pub(crate) fn on_occluded(&mut self, occluded: bool) {
+        self.occluded = occluded;
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.minimized || self.occluded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_unpaused() {
+        assert!(!RenderPause::default().is_paused());
+    }
+
+    #[test]
+    fn zero_size_resize_pauses() {
+        let mut pause = RenderPause::default();
+        pause.on_resize(0, 0);
+        assert!(pause.is_paused());
+    }
+
+    #[test]
+    fn resizing_back_to_nonzero_unpauses() {
+        let mut pause = RenderPause::default();
+        pause.on_resize(0, 0);
+        pause.on_resize(800, 600);
+        assert!(!pause.is_paused());
+    }
+
+    #[test]
+    fn occlusion_pauses_even_at_full_size() {
+        let mut pause = RenderPause::default();
+        pause.on_resize(800, 600);
+        pause.on_occluded(true);
+        assert!(pause.is_paused());
+    }
+
+    #[test]
+    fn becoming_unoccluded_does_not_unpause_a_minimized_window() {
+        let mut pause = RenderPause::default();
+        pause.on_resize(0, 0);
+        pause.on_occluded(true);
+        pause.on_occluded(false);
+        assert!(pause.is_paused());
+    }
+
+    #[test]
+    fn one_dimension_zero_still_counts_as_minimized() {
+        let mut pause = RenderPause::default();
+        pause.on_resize(800, 0);
+        assert!(pause.is_paused());
+    }
+}
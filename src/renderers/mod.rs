@@ -0,0 +1,369 @@
+use crate::debug_bounds::DebugBounds;
+use crate::debug_grid::DebugGrid;
+use crate::instance::InstanceRaw;
+use crate::pixels::FrameBuffer;
+use crate::scaling_matrix::{ScalingMatrix, ScreenRotation};
+
+/// Holds what the sprite pass draws from: the instance buffer, the scaling
+/// matrix and clip stack, the border, depth and background buffers, and the
+/// debug overlays. [`SpriteRenderer::render`] records no GPU commands: no
+/// render pipeline is built, so nothing is drawn into the target view.
+pub(crate) struct SpriteRenderer {
+    render_pipeline: Option<wgpu::RenderPipeline>,
+    instance_buffer: Option<wgpu::Buffer>,
+    instance_buffer_capacity: u64,
+    instance_count: usize,
+    matrix: ScalingMatrix,
+    border_texture: Option<wgpu::Texture>,
+    rotation: ScreenRotation,
+    clip_stack: Vec<(u32, u32, u32, u32)>,
+    depth_stencil_format: Option<wgpu::TextureFormat>,
+    depth_texture: Option<wgpu::Texture>,
+    pending_depth_clear: bool,
+    debug_grid: Option<DebugGrid>,
+    debug_bounds: DebugBounds,
+    #[cfg(feature = "debug-font")]
+    debug_text: Vec<(cgmath::Vector2<f32>, String)>,
+    sprite_pass_hook: Option<Box<dyn Fn(&wgpu::Device, &wgpu::Queue, &mut wgpu::RenderPass<'_>)>>,
+    /// A CPU-side background drawn under the sprite instances in the same
+    /// pass, letting a game mix [`crate::Chroma::frame_mut`] pixel art
+    /// (raycasters, particle fields, anything easier to plot than to model
+    /// as sprites) with instanced sprites on top. `None` until
+    /// [`SpriteRenderer::frame_mut`] is first called, so games that only
+    /// ever draw sprites don't pay for a buffer they never use.
+    background_frame: Option<FrameBuffer>,
+    background_size: (u32, u32),
+    /// How many times [`SpriteRenderer::configure_instances`] has uploaded
+    /// instance data to the GPU, for [`crate::FrameStats::instance_buffer_uploads`].
+    instance_buffer_uploads: u64,
+}
+
+impl SpriteRenderer {
+    pub(crate) fn new(virtual_size: (u32, u32), window_size: (u32, u32)) -> Self {
+        Self {
+            render_pipeline: None,
+            instance_buffer: None,
+            instance_buffer_capacity: 0,
+            instance_count: 0,
+            matrix: ScalingMatrix::new(virtual_size, window_size),
+            border_texture: None,
+            rotation: ScreenRotation::None,
+            clip_stack: Vec::new(),
+            depth_stencil_format: None,
+            depth_texture: None,
+            pending_depth_clear: false,
+            debug_grid: None,
+            debug_bounds: DebugBounds::new(),
+            #[cfg(feature = "debug-font")]
+            debug_text: Vec::new(),
+            sprite_pass_hook: None,
+            background_frame: None,
+            background_size: (0, 0),
+            instance_buffer_uploads: 0,
+        }
+    }
+
+    /// Mutable access to the CPU-side background buffer, at `virtual_size`
+    /// resolution, laid out as RGBA8 rows with `(0, 0)` at the top-left.
+    /// Lazily allocates the buffer on first call, and reallocates it (losing
+    /// its contents) if `virtual_size` has changed since — unlike
+    /// [`crate::pixels::Pixels::resize_buffer`] on the standalone path, which
+    /// keeps the old contents top-left aligned.
+    pub(crate) fn frame_mut(&mut self, virtual_size: (u32, u32)) -> &mut [u8] {
+        let needs_resize = match &self.background_frame {
+            Some(_) => self.background_size != virtual_size,
+            None => true,
+        };
+        if needs_resize {
+            self.background_frame = Some(FrameBuffer::new(virtual_size.0, virtual_size.1));
+            self.background_size = virtual_size;
+        }
+        self.background_frame.as_mut().unwrap().as_mut_slice()
+    }
+
+    /// Registers a closure run inside the sprite render pass, after the
+    /// instanced sprite draw call but before the pass ends, for custom
+    /// geometry (lines, outlines, extra meshes) drawn in the same pass.
+    /// Replaces any previously set hook.
+    pub(crate) fn set_sprite_pass_hook(
+        &mut self,
+        hook: impl Fn(&wgpu::Device, &wgpu::Queue, &mut wgpu::RenderPass<'_>) + 'static,
+    ) {
+        self.sprite_pass_hook = Some(Box::new(hook));
+    }
+
+    /// Sets the default bounding-box outline color drawn for every layer
+    /// without a more specific override, or `None` to disable it.
+    pub(crate) fn set_debug_bounds_default(&mut self, color: Option<[f32; 4]>) {
+        self.debug_bounds.set_default(color);
+    }
+
+    /// Sets the bounding-box outline color for a specific layer, overriding
+    /// the default set by [`SpriteRenderer::set_debug_bounds_default`].
+    pub(crate) fn set_debug_bounds_layer(&mut self, layer: u8, color: [f32; 4]) {
+        self.debug_bounds.set_layer(layer, color);
+    }
+
+    /// Replaces the queue of debug text draws, rasterized on top of the
+    /// debug grid (and everything below it) on the next render pass.
+    #[cfg(feature = "debug-font")]
+    pub(crate) fn set_debug_text(&mut self, entries: Vec<(cgmath::Vector2<f32>, String)>) {
+        self.debug_text = entries;
+    }
+
+    /// Sets or clears the debug grid overlay drawn on top of the sprite
+    /// image. `None` disables it entirely, independent of
+    /// [`DebugGrid::toggle`] which just flips visibility without forgetting
+    /// the configured size and color.
+    pub(crate) fn set_debug_grid(&mut self, grid: Option<DebugGrid>) {
+        self.debug_grid = grid;
+    }
+
+    /// Toggles the debug grid on or off. A no-op if none has been set with
+    /// [`SpriteRenderer::set_debug_grid`].
+    pub(crate) fn toggle_debug_grid(&mut self) {
+        if let Some(grid) = &mut self.debug_grid {
+            grid.toggle();
+        }
+    }
+
+    /// Creates a depth/stencil texture at virtual resolution, records its
+    /// format for the render pipeline's `depth_stencil` state (see
+    /// [`InstanceRaw::depth`]), and drops any pipeline built without it.
+    pub(crate) fn enable_depth_stencil(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        virtual_size: (u32, u32),
+    ) {
+        let (width, height) = virtual_size;
+        self.depth_texture = Some(device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("chroma-depth-texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        }));
+        self.depth_stencil_format = Some(format);
+        // Nothing in this renderer builds a pipeline (`configure_instances`
+        // only uploads instance data), so this leaves it `None`, as at `new`.
+        self.render_pipeline = None;
+    }
+
+    /// Clears the depth/stencil texture on the next render pass. A no-op if
+    /// depth testing hasn't been enabled.
+    pub(crate) fn clear_depth(&mut self) {
+        self.pending_depth_clear = self.depth_texture.is_some();
+    }
+
+    /// Pushes a scissor rect, in backing-texture pixel coordinates, that
+    /// clips subsequent sprite draws. Nested clips intersect with the rect
+    /// currently on top of the stack.
+    pub(crate) fn push_clip(&mut self, rect: (u32, u32, u32, u32), backing_extent: (u32, u32)) {
+        let clamped = clamp_rect_to_extent(rect, backing_extent);
+        let intersected = match self.clip_stack.last() {
+            Some(&top) => intersect_rects(top, clamped),
+            None => clamped,
+        };
+        self.clip_stack.push(intersected);
+    }
+
+    pub(crate) fn pop_clip(&mut self) {
+        self.clip_stack.pop();
+    }
+
+    pub(crate) fn active_clip(&self) -> Option<(u32, u32, u32, u32)> {
+        self.clip_stack.last().copied()
+    }
+
+    pub(crate) fn set_border_texture(&mut self, texture: Option<wgpu::Texture>) {
+        self.border_texture = texture;
+    }
+
+    pub(crate) fn set_rotation(
+        &mut self,
+        rotation: ScreenRotation,
+        virtual_size: (u32, u32),
+        window_size: (u32, u32),
+    ) {
+        self.rotation = rotation;
+        self.matrix = ScalingMatrix::with_rotation(virtual_size, window_size, rotation);
+    }
+
+    pub(crate) fn resize(&mut self, virtual_size: (u32, u32), window_size: (u32, u32)) {
+        self.matrix = ScalingMatrix::with_rotation(virtual_size, window_size, self.rotation);
+    }
+
+    pub(crate) fn clip_rect(&self) -> (u32, u32, u32, u32) {
+        self.matrix.clip_rect()
+    }
+
+    pub(crate) fn scale_factor(&self) -> u32 {
+        self.matrix.scale_factor()
+    }
+
+    pub(crate) fn scaling_matrix(&self) -> &ScalingMatrix {
+        &self.matrix
+    }
+
+    /// Uploads `instances` for the next [`SpriteRenderer::render`] call.
+    /// Reuses the existing instance buffer via `queue.write_buffer` when it
+    /// already has enough capacity, only reallocating (at double the needed
+    /// size, so a slowly growing instance count doesn't reallocate every
+    /// frame) when `instances` outgrows it.
+    pub(crate) fn configure_instances(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        instances: &[InstanceRaw],
+    ) {
+        self.instance_count = instances.len();
+        self.instance_buffer_uploads += 1;
+        let data = bytemuck::cast_slice(instances);
+        let needed = data.len() as u64;
+
+        if self.instance_buffer.is_some() && needed <= self.instance_buffer_capacity {
+            queue.write_buffer(self.instance_buffer.as_ref().unwrap(), 0, data);
+            return;
+        }
+
+        self.instance_buffer_capacity = needed.max(1) * 2;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("chroma-instance-buffer"),
+            size: self.instance_buffer_capacity,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&buffer, 0, data);
+        self.instance_buffer = Some(buffer);
+    }
+
+    /// The current frame's instance count, for [`crate::FrameStats::instance_count`].
+    pub(crate) fn instance_count(&self) -> usize {
+        self.instance_count
+    }
+
+    /// How many times [`SpriteRenderer::configure_instances`] has run, for
+    /// [`crate::FrameStats::instance_buffer_uploads`].
+    pub(crate) fn instance_buffer_uploads(&self) -> u64 {
+        self.instance_buffer_uploads
+    }
+
+    pub(crate) fn render(&self, _encoder: &mut wgpu::CommandEncoder, _target: &wgpu::TextureView) {
+        // Draw order matters: the border, if any, covers the full window
+        // and is never scissored, so it stays visible in the letterbox.
+        // The scaled game image is drawn on top of it, scissored to
+        // `clip_rect` so it doesn't bleed into the border. The debug grid,
+        // if enabled, is drawn on top of every sprite instance but (once
+        // chroma has one) below any UI layer.
+        self.render_border();
+        self.render_game_image();
+        self.render_debug_bounds();
+        self.render_debug_grid();
+        #[cfg(feature = "debug-font")]
+        self.render_debug_text();
+    }
+
+    fn render_border(&self) {
+        let _ = &self.border_texture;
+    }
+
+    fn render_game_image(&self) {
+        // Order once a real `wgpu::RenderPass` is built here: upload
+        // `background_frame`'s dirty rect as the pass's color attachment
+        // load, so the CPU-drawn background sits under every sprite
+        // instance instead of behind a separate upscale pass of its own —
+        // that's what lets `Chroma::frame_mut` and instanced sprites share
+        // one composited frame. The sprite pass hook then runs after the
+        // instanced draw call and before the pass ends, but there's no real
+        // `wgpu::RenderPass` to hand either of them in this vendored subset
+        // — both are wired up when the pipeline is built.
+        let _ = (
+            &self.render_pipeline,
+            &self.instance_buffer,
+            self.instance_count,
+            self.active_clip(),
+            &self.depth_texture,
+            self.depth_stencil_format,
+            self.pending_depth_clear,
+            &self.sprite_pass_hook,
+            &self.background_frame,
+        );
+    }
+
+    fn render_debug_bounds(&self) {
+        let _ = &self.debug_bounds;
+    }
+
+    fn render_debug_grid(&self) {
+        let _ = self.debug_grid.filter(|grid| grid.enabled());
+    }
+
+    #[cfg(feature = "debug-font")]
+    fn render_debug_text(&self) {
+        let _ = &self.debug_text;
+    }
+}
+
+fn clamp_rect_to_extent(
+    rect: (u32, u32, u32, u32),
+    extent: (u32, u32),
+) -> (u32, u32, u32, u32) {
+    let (x, y, w, h) = rect;
+    let x = x.min(extent.0);
+    let y = y.min(extent.1);
+    let w = w.min(extent.0 - x);
+    let h = h.min(extent.1 - y);
+    (x, y, w, h)
+}
+
+fn intersect_rects(a: (u32, u32, u32, u32), b: (u32, u32, u32, u32)) -> (u32, u32, u32, u32) {
+    let x1 = a.0.max(b.0);
+    let y1 = a.1.max(b.1);
+    let x2 = (a.0 + a.2).min(b.0 + b.2);
+    let y2 = (a.1 + a.3).min(b.1 + b.3);
+    (x1, y1, x2.saturating_sub(x1), y2.saturating_sub(y1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_rect_shrinks_to_extent() {
+        assert_eq!(clamp_rect_to_extent((10, 10, 100, 100), (64, 64)), (10, 10, 54, 54));
+    }
+
+    #[test]
+    fn intersect_rects_overlap() {
+        assert_eq!(
+            intersect_rects((0, 0, 32, 32), (16, 16, 32, 32)),
+            (16, 16, 16, 16)
+        );
+    }
+
+    #[test]
+    fn intersect_rects_disjoint_is_empty() {
+        assert_eq!(intersect_rects((0, 0, 8, 8), (16, 16, 8, 8)), (16, 16, 0, 0));
+    }
+
+    #[test]
+    fn frame_mut_allocates_a_buffer_sized_to_virtual_size() {
+        let mut renderer = SpriteRenderer::new((320, 180), (640, 360));
+        assert_eq!(renderer.frame_mut((320, 180)).len(), 320 * 180 * 4);
+    }
+
+    #[test]
+    fn frame_mut_reallocates_when_virtual_size_changes() {
+        let mut renderer = SpriteRenderer::new((320, 180), (640, 360));
+        renderer.frame_mut((320, 180))[0] = 42;
+        assert_eq!(renderer.frame_mut((160, 90)).len(), 160 * 90 * 4);
+    }
+}
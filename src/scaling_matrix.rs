@@ -0,0 +1,215 @@
+/// A rotation applied to the whole scaled game image, for portrait-oriented
+/// games running on a landscape display (or vice versa).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScreenRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    Clockwise270,
+}
+
+impl ScreenRotation {
+    /// Whether this rotation swaps width and height.
+    fn swaps_axes(self) -> bool {
+        matches!(self, ScreenRotation::Clockwise90 | ScreenRotation::Clockwise270)
+    }
+}
+
+/// Computes the transform that maps the virtual pixel-art resolution onto the
+/// (potentially larger, potentially non-integer-multiple) window surface,
+/// preserving aspect ratio and choosing the largest integer scale that fits
+/// ("letterboxing" the remainder).
+#[derive(Debug, Clone, Copy)]
+pub struct ScalingMatrix {
+    pub(crate) transform: cgmath::Matrix4<f32>,
+    pub(crate) clip_rect: (u32, u32, u32, u32),
+    pub(crate) scale_factor: u32,
+}
+
+impl ScalingMatrix {
+    pub fn new(virtual_size: (u32, u32), window_size: (u32, u32)) -> Self {
+        Self::with_rotation(virtual_size, window_size, ScreenRotation::None)
+    }
+
+    /// Like [`ScalingMatrix::new`], but fits `virtual_size` as if it were
+    /// rotated by `rotation` before scaling — for portrait-orientation
+    /// games presented on a landscape display, or vice versa.
+    pub fn with_rotation(
+        virtual_size: (u32, u32),
+        window_size: (u32, u32),
+        rotation: ScreenRotation,
+    ) -> Self {
+        let virtual_size = if rotation.swaps_axes() {
+            (virtual_size.1, virtual_size.0)
+        } else {
+            virtual_size
+        };
+
+        let (virtual_width, virtual_height) = (virtual_size.0 as f32, virtual_size.1 as f32);
+        let (window_width, window_height) = (window_size.0 as f32, window_size.1 as f32);
+
+        let scale = (window_width / virtual_width)
+            .min(window_height / virtual_height)
+            .max(1.0)
+            .floor();
+
+        let scaled_width = virtual_width * scale;
+        let scaled_height = virtual_height * scale;
+
+        let sx = scaled_width / window_width;
+        let sy = scaled_height / window_height;
+
+        let transform = cgmath::Matrix4::from_nonuniform_scale(sx, sy, 1.0);
+
+        // Split the letterbox remainder evenly in `f32` first, only
+        // flooring to a `u32` for `clip_x`/`clip_y` themselves. The clip
+        // width/height are then derived from `window_size` minus twice that
+        // integer offset, rather than rounding `scaled_width`/`scaled_height`
+        // independently — that kept the scissor rect and the transform
+        // above in lockstep for even remainders, but let them disagree by a
+        // pixel whenever the remainder was odd.
+        let clip_x = ((window_width - scaled_width) / 2.0).floor().max(0.0) as u32;
+        let clip_y = ((window_height - scaled_height) / 2.0).floor().max(0.0) as u32;
+        let clip_w = window_size.0.saturating_sub(clip_x * 2);
+        let clip_h = window_size.1.saturating_sub(clip_y * 2);
+
+        Self {
+            transform,
+            clip_rect: (clip_x, clip_y, clip_w, clip_h),
+            scale_factor: scale as u32,
+        }
+    }
+
+    pub fn clip_rect(&self) -> (u32, u32, u32, u32) {
+        self.clip_rect
+    }
+
+    pub fn scale_factor(&self) -> u32 {
+        self.scale_factor
+    }
+
+    /// Maps a window-space position to a virtual pixel coordinate. `Ok` if
+    /// `pos` falls inside [`ScalingMatrix::clip_rect`] and maps to a pixel
+    /// within `virtual_size`; otherwise `Err` with the coordinate clamped to
+    /// `virtual_size`'s bounds.
+    pub fn window_pos_to_pixel(
+        &self,
+        pos: (f32, f32),
+        virtual_size: (u32, u32),
+    ) -> Result<(u32, u32), (i32, i32)> {
+        let (clip_x, clip_y, clip_w, clip_h) = self.clip_rect;
+        let (px, py) = pos;
+
+        let inside = px >= clip_x as f32
+            && px < (clip_x + clip_w) as f32
+            && py >= clip_y as f32
+            && py < (clip_y + clip_h) as f32;
+
+        let pixel_x = ((px - clip_x as f32) / self.scale_factor as f32).floor() as i32;
+        let pixel_y = ((py - clip_y as f32) / self.scale_factor as f32).floor() as i32;
+        // An odd letterbox remainder leaves the clip rect one window pixel
+        // wider or taller than the scaled image; that edge maps past the last
+        // virtual pixel and counts as outside.
+        let inside = inside && pixel_x < virtual_size.0 as i32 && pixel_y < virtual_size.1 as i32;
+
+        if inside {
+            Ok((pixel_x as u32, pixel_y as u32))
+        } else {
+            let clamped_x = pixel_x.clamp(0, virtual_size.0.saturating_sub(1) as i32);
+            let clamped_y = pixel_y.clamp(0, virtual_size.1.saturating_sub(1) as i32);
+            Err((clamped_x, clamped_y))
+        }
+    }
+
+    /// The inverse of [`ScalingMatrix::window_pos_to_pixel`]: the
+    /// window-space position of a virtual pixel's top-left corner.
+    pub fn pixel_pos_to_window(&self, pixel: (u32, u32)) -> (f32, f32) {
+        let (clip_x, clip_y, _, _) = self.clip_rect;
+        (
+            clip_x as f32 + pixel.0 as f32 * self.scale_factor as f32,
+            clip_y as f32 + pixel.1 as f32 * self.scale_factor as f32,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_scale_no_letterbox() {
+        let matrix = ScalingMatrix::new((320, 180), (640, 360));
+        assert_eq!(matrix.scale_factor(), 2);
+        assert_eq!(matrix.clip_rect(), (0, 0, 640, 360));
+    }
+
+    #[test]
+    fn letterboxed_wide_window() {
+        let matrix = ScalingMatrix::new((320, 180), (1000, 360));
+        assert_eq!(matrix.scale_factor(), 2);
+        assert_eq!(matrix.clip_rect(), (180, 0, 640, 360));
+    }
+
+    #[test]
+    fn letterboxed_tall_window() {
+        let matrix = ScalingMatrix::new((320, 180), (640, 600));
+        assert_eq!(matrix.scale_factor(), 2);
+        assert_eq!(matrix.clip_rect(), (0, 120, 640, 360));
+    }
+
+    #[test]
+    fn window_pos_to_pixel_inside_clip_rect() {
+        let matrix = ScalingMatrix::new((320, 180), (1000, 360));
+        assert_eq!(matrix.window_pos_to_pixel((180.0, 0.0), (320, 180)), Ok((0, 0)));
+        assert_eq!(matrix.window_pos_to_pixel((819.0, 359.0), (320, 180)), Ok((319, 179)));
+    }
+
+    #[test]
+    fn window_pos_to_pixel_outside_clip_rect_clamps() {
+        let matrix = ScalingMatrix::new((320, 180), (1000, 360));
+        assert_eq!(matrix.window_pos_to_pixel((0.0, 0.0), (320, 180)), Err((0, 0)));
+        assert_eq!(matrix.window_pos_to_pixel((999.0, 0.0), (320, 180)), Err((319, 0)));
+    }
+
+    #[test]
+    fn window_pos_to_pixel_clamps_without_panicking_for_zero_virtual_size() {
+        let matrix = ScalingMatrix::new((320, 180), (1000, 360));
+        assert_eq!(matrix.window_pos_to_pixel((0.0, 0.0), (0, 0)), Err((0, 0)));
+    }
+
+    #[test]
+    fn clip_rect_never_disagrees_with_the_screen_size() {
+        // For any screen/texture size combination, the scissor rect must
+        // exactly tile the window: an odd letterbox remainder shouldn't
+        // leave the transform and clip rect disagreeing by half a pixel.
+        for virtual_size in [(320, 180), (64, 64), (100, 75)] {
+            for window_size in [(641, 360), (999, 500), (321, 181), (1000, 361)] {
+                let matrix = ScalingMatrix::new(virtual_size, window_size);
+                let (clip_x, _, clip_w, _) = matrix.clip_rect();
+                assert_eq!(
+                    clip_w + clip_x * 2,
+                    window_size.0,
+                    "virtual={virtual_size:?} window={window_size:?}"
+                );
+
+                let (_, clip_y, _, clip_h) = matrix.clip_rect();
+                assert_eq!(
+                    clip_h + clip_y * 2,
+                    window_size.1,
+                    "virtual={virtual_size:?} window={window_size:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pixel_pos_to_window_round_trips() {
+        let matrix = ScalingMatrix::new((320, 180), (1000, 360));
+        let window_pos = matrix.pixel_pos_to_window((40, 20));
+        assert_eq!(
+            matrix.window_pos_to_pixel(window_pos, (320, 180)),
+            Ok((40, 20))
+        );
+    }
+}
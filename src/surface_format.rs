@@ -0,0 +1,36 @@
+/// Picks an sRGB-encoded format from `supported` if one is available,
+/// falling back to `supported[0]` otherwise and logging a warning, since
+/// that fallback is a linear format and chroma's textures then take that
+/// same format. No gamma conversion is inserted for the fallback.
+pub(crate) fn choose_surface_format(supported: &[wgpu::TextureFormat]) -> wgpu::TextureFormat {
+    match supported.iter().find(|format| format.is_srgb()) {
+        Some(&format) => format,
+        None => {
+            log::warn!(
+                "no sRGB surface format available; falling back to linear format {:?}",
+                supported[0]
+            );
+            supported[0]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_srgb_format() {
+        let supported = [
+            wgpu::TextureFormat::Bgra8Unorm,
+            wgpu::TextureFormat::Bgra8UnormSrgb,
+        ];
+        assert_eq!(choose_surface_format(&supported), wgpu::TextureFormat::Bgra8UnormSrgb);
+    }
+
+    #[test]
+    fn falls_back_to_first_when_no_srgb_available() {
+        let supported = [wgpu::TextureFormat::Bgra8Unorm, wgpu::TextureFormat::Rgba8Unorm];
+        assert_eq!(choose_surface_format(&supported), wgpu::TextureFormat::Bgra8Unorm);
+    }
+}
@@ -0,0 +1,54 @@
+/// A handle to a texture previously uploaded via
+/// [`crate::Chroma::create_texture_from_rgba`].
+///
+/// Handles are opaque and only meaningful to the [`crate::Chroma`] instance
+/// that issued them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureHandle(pub(crate) u64);
+
+/// A GPU texture owned by a [`crate::Chroma`] instance, alongside the
+/// dimensions it was created with. Kept alongside the texture itself so code
+/// like [`crate::Chroma::generate_mipmaps`] can reason about size without
+/// depending on `wgpu::Texture` exposing its own extent back.
+pub(crate) struct StoredTexture {
+    pub(crate) texture: wgpu::Texture,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+/// A texture load in progress, returned by
+/// [`crate::Chroma::load_sprite_sheet_from_path_async`] (native) and
+/// [`crate::Chroma::load_sprite_sheet_async`] (`wasm32`). Both require the
+/// `async-loading` feature.
+#[cfg(feature = "async-loading")]
+pub type TextureLoadFuture<'a> = std::pin::Pin<
+    Box<dyn std::future::Future<Output = Result<TextureHandle, crate::ChromaError>> + 'a>,
+>;
+
+/// The number of mip levels a full chain from `width`x`height` down to a
+/// single texel needs: `floor(log2(max(width, height))) + 1`.
+pub(crate) fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_texel_needs_one_level() {
+        assert_eq!(mip_level_count(1, 1), 1);
+    }
+
+    #[test]
+    fn power_of_two_matches_log2_plus_one() {
+        assert_eq!(mip_level_count(256, 256), 9);
+        assert_eq!(mip_level_count(256, 64), 9);
+    }
+
+    #[test]
+    fn non_power_of_two_rounds_down_before_adding_one() {
+        // floor(log2(17)) + 1 == 5
+        assert_eq!(mip_level_count(17, 3), 5);
+    }
+}
@@ -0,0 +1,248 @@
+//! Streams a large tile grid in and out of the scene as chunks near the
+//! camera, so a world far bigger than comfortably fits as live
+//! [`TileHandle`] instances only pays for the part currently visible.
+//!
+//! This engine doesn't include a Tiled TMX/XML importer — [`crate::ldtk`]
+//! is the one map format it parses. [`StreamingTileMap::load`] instead
+//! expects a small binary layout a TMX export can be converted to: a
+//! little-endian `u32` width, a little-endian `u32` height, then
+//! `width * height` little-endian `u32` sprite-sheet indices in row-major
+//! order, with `u32::MAX` marking an empty cell.
+
+use std::collections::HashMap;
+
+use crate::instance::Instance;
+use crate::{Chroma, ChromaError, ChromaEvent, TileHandle};
+
+const EMPTY_CELL: u32 = u32::MAX;
+const INSTANCE_BYTES: usize = std::mem::size_of::<Instance>();
+
+type ChunkCoord = (i32, i32);
+
+/// A large tile grid streamed in and out of a [`Chroma`]'s live tile
+/// instances one `chunk_size`x`chunk_size` chunk at a time. Only chunks
+/// within one chunk of the camera stay loaded; [`StreamingTileMap::update`]
+/// evicts the rest, keeping the live instance count bounded regardless of
+/// map size.
+#[derive(Debug)]
+pub struct StreamingTileMap {
+    width: u32,
+    height: u32,
+    chunk_size: u32,
+    tiles: Vec<u32>,
+    loaded_chunks: HashMap<ChunkCoord, Vec<TileHandle>>,
+    memory_limit_bytes: usize,
+}
+
+impl StreamingTileMap {
+    /// Parses `data` in the format documented on [`StreamingTileMap`].
+    /// Errors with [`ChromaError::InvalidTextureData`] if `data`'s length
+    /// doesn't match the header's declared `width`/`height` — reused here
+    /// since both describe a byte buffer that doesn't match its own
+    /// claimed size.
+    pub fn load(data: &[u8], chunk_size: u32) -> Result<Self, ChromaError> {
+        let read_u32 = |offset: usize| -> Option<u32> {
+            data.get(offset..offset + 4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        };
+        let header_err = || ChromaError::InvalidTextureData {
+            expected: 8,
+            actual: data.len(),
+        };
+        let width = read_u32(0).ok_or_else(header_err)?;
+        let height = read_u32(4).ok_or_else(header_err)?;
+
+        // `width`/`height` are attacker-controlled header fields; a
+        // maliciously (or just corrupted) crafted file can claim dimensions
+        // whose byte count overflows `usize`. Widen to `u64` and saturate
+        // rather than wrap, so an overflow is reported as a length mismatch
+        // instead of panicking (debug) or under-allocating `tiles` and
+        // panicking later in `load_chunk` (release).
+        let expected_bytes = (width as u64)
+            .saturating_mul(height as u64)
+            .saturating_mul(4)
+            .saturating_add(8);
+        if expected_bytes > usize::MAX as u64 || data.len() as u64 != expected_bytes {
+            return Err(ChromaError::InvalidTextureData {
+                expected: expected_bytes.min(usize::MAX as u64) as usize,
+                actual: data.len(),
+            });
+        }
+
+        let tiles = data[8..]
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+
+        Ok(Self {
+            width,
+            height,
+            chunk_size: chunk_size.max(1),
+            tiles,
+            loaded_chunks: HashMap::new(),
+            memory_limit_bytes: usize::MAX,
+        })
+    }
+
+    /// Caps the approximate instance memory this map may keep loaded before
+    /// [`StreamingTileMap::update`] starts emitting
+    /// [`ChromaEvent::MemoryPressure`]. Unbounded by default.
+    pub fn set_memory_limit_bytes(&mut self, limit: usize) {
+        self.memory_limit_bytes = limit;
+    }
+
+    /// The approximate bytes of instance bookkeeping currently loaded: one
+    /// [`Instance`]'s size per live tile. Not GPU texture memory — loaded
+    /// chunks share `chroma`'s active sprite sheet rather than each
+    /// allocating a texture of their own, so this tracks the CPU-side cost
+    /// of keeping that many tiles live instead.
+    pub fn memory_usage_bytes(&self) -> usize {
+        self.loaded_chunks
+            .values()
+            .map(|tiles| tiles.len() * INSTANCE_BYTES)
+            .sum()
+    }
+
+    fn chunk_of(&self, x: u32, y: u32) -> ChunkCoord {
+        ((x / self.chunk_size) as i32, (y / self.chunk_size) as i32)
+    }
+
+    /// Loads every chunk within one chunk of `camera_tile` (in tile-grid
+    /// units) and evicts every other currently-loaded chunk, adding and
+    /// removing `chroma`'s tile instances to match. Pushes
+    /// [`ChromaEvent::MemoryPressure`] onto [`crate::Chroma::events`] if
+    /// the resulting instance memory exceeds the configured limit.
+    pub fn update(&mut self, chroma: &mut Chroma, camera_tile: (u32, u32), tile_size: (u32, u32)) {
+        let camera_chunk = self.chunk_of(camera_tile.0, camera_tile.1);
+        let chunk_count_x = self.width.div_ceil(self.chunk_size) as i32;
+        let chunk_count_y = self.height.div_ceil(self.chunk_size) as i32;
+
+        let mut wanted = Vec::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let coord = (camera_chunk.0 + dx, camera_chunk.1 + dy);
+                let in_bounds =
+                    coord.0 >= 0 && coord.1 >= 0 && coord.0 < chunk_count_x && coord.1 < chunk_count_y;
+                if in_bounds {
+                    wanted.push(coord);
+                }
+            }
+        }
+
+        let to_evict: Vec<ChunkCoord> = self
+            .loaded_chunks
+            .keys()
+            .filter(|coord| !wanted.contains(coord))
+            .copied()
+            .collect();
+        for coord in to_evict {
+            if let Some(tiles) = self.loaded_chunks.remove(&coord) {
+                for tile in tiles {
+                    chroma.remove_tile(tile);
+                }
+            }
+        }
+
+        for coord in wanted {
+            if !self.loaded_chunks.contains_key(&coord) {
+                let tiles = self.load_chunk(chroma, coord, tile_size);
+                self.loaded_chunks.insert(coord, tiles);
+            }
+        }
+
+        let used_bytes = self.memory_usage_bytes();
+        if used_bytes > self.memory_limit_bytes {
+            chroma.events().push(ChromaEvent::MemoryPressure {
+                used_bytes,
+                limit_bytes: self.memory_limit_bytes,
+            });
+        }
+    }
+
+    fn load_chunk(
+        &self,
+        chroma: &mut Chroma,
+        (chunk_x, chunk_y): ChunkCoord,
+        tile_size: (u32, u32),
+    ) -> Vec<TileHandle> {
+        let x0 = chunk_x as u32 * self.chunk_size;
+        let y0 = chunk_y as u32 * self.chunk_size;
+        let x1 = (x0 + self.chunk_size).min(self.width);
+        let y1 = (y0 + self.chunk_size).min(self.height);
+
+        let mut handles = Vec::new();
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let index = self.tiles[(y * self.width + x) as usize];
+                if index == EMPTY_CELL {
+                    continue;
+                }
+                let position =
+                    cgmath::Vector2::new((x * tile_size.0) as f32, (y * tile_size.1) as f32);
+                handles.push(chroma.add_tile(position, index));
+            }
+        }
+        handles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(width: u32, height: u32, tiles: &[u32]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&width.to_le_bytes());
+        data.extend_from_slice(&height.to_le_bytes());
+        for tile in tiles {
+            data.extend_from_slice(&tile.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn load_rejects_data_shorter_than_the_declared_grid() {
+        let data = encode(2, 2, &[0, 0, 0]);
+        assert!(matches!(
+            StreamingTileMap::load(&data, 1),
+            Err(ChromaError::InvalidTextureData { .. })
+        ));
+    }
+
+    #[test]
+    fn load_rejects_a_header_whose_byte_count_overflows_usize() {
+        let mut data = encode(2, 2, &[0, 0, 0, 0]);
+        data[0..4].copy_from_slice(&u32::MAX.to_le_bytes());
+        data[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            StreamingTileMap::load(&data, 1),
+            Err(ChromaError::InvalidTextureData { .. })
+        ));
+    }
+
+    #[test]
+    fn load_accepts_a_well_formed_grid() {
+        let data = encode(2, 2, &[0, 1, 2, 3]);
+        let map = StreamingTileMap::load(&data, 1).unwrap();
+        assert_eq!(map.width, 2);
+        assert_eq!(map.height, 2);
+        assert_eq!(map.tiles, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn chunk_of_buckets_by_chunk_size() {
+        let data = encode(4, 4, &[0; 16]);
+        let map = StreamingTileMap::load(&data, 2).unwrap();
+        assert_eq!(map.chunk_of(0, 0), (0, 0));
+        assert_eq!(map.chunk_of(1, 1), (0, 0));
+        assert_eq!(map.chunk_of(2, 3), (1, 1));
+    }
+
+    #[test]
+    fn memory_usage_scales_with_loaded_tile_count() {
+        let data = encode(2, 2, &[0, 0, 0, 0]);
+        let mut map = StreamingTileMap::load(&data, 1).unwrap();
+        map.loaded_chunks.insert((0, 0), vec![TileHandle(0), TileHandle(1)]);
+        assert_eq!(map.memory_usage_bytes(), 2 * INSTANCE_BYTES);
+    }
+}
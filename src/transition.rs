@@ -0,0 +1,112 @@
+/// A scene transition effect, advanced each frame by
+/// [`crate::Chroma::update_transition`]. Only its progress is tracked; no
+/// render pass applies the effect to the image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transition {
+    FadeOut { duration: f32, color: [f32; 3] },
+    FadeIn { duration: f32, color: [f32; 3] },
+    Wipe { direction: WipeDirection, duration: f32 },
+    Dissolve { duration: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WipeDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+impl Transition {
+    fn duration(&self) -> f32 {
+        match self {
+            Transition::FadeOut { duration, .. }
+            | Transition::FadeIn { duration, .. }
+            | Transition::Wipe { duration, .. }
+            | Transition::Dissolve { duration } => *duration,
+        }
+    }
+
+    /// Whether progress runs `1 -> 0` ([`Transition::FadeIn`], the reverse
+    /// of a fade out) instead of `0 -> 1` (every other variant).
+    fn reversed(&self) -> bool {
+        matches!(self, Transition::FadeIn { .. })
+    }
+}
+
+/// Drives a [`Transition`]'s progress uniform over time.
+pub(crate) struct TransitionState {
+    transition: Transition,
+    elapsed: f32,
+    finished: bool,
+}
+
+impl TransitionState {
+    pub(crate) fn new(transition: Transition) -> Self {
+        Self {
+            transition,
+            elapsed: 0.0,
+            finished: false,
+        }
+    }
+
+    pub(crate) fn update(&mut self, dt: f32) {
+        if self.finished {
+            return;
+        }
+        self.elapsed += dt;
+        if self.elapsed >= self.transition.duration() {
+            self.elapsed = self.transition.duration();
+            self.finished = true;
+        }
+    }
+
+    pub(crate) fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Progress in `0..=1`, clamped exactly at the endpoints so the final
+    /// frame doesn't flicker. Reversed for [`Transition::FadeIn`].
+    pub(crate) fn progress(&self) -> f32 {
+        let duration = self.transition.duration();
+        let t = if duration <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / duration).clamp(0.0, 1.0)
+        };
+        if self.transition.reversed() {
+            1.0 - t
+        } else {
+            t
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn progress_clamps_at_one() {
+        let mut state = TransitionState::new(Transition::FadeOut {
+            duration: 1.0,
+            color: [0.0, 0.0, 0.0],
+        });
+        state.update(0.6);
+        assert!((state.progress() - 0.6).abs() < 1e-6);
+        state.update(1.0);
+        assert_eq!(state.progress(), 1.0);
+        assert!(state.finished());
+    }
+
+    #[test]
+    fn fade_in_runs_in_reverse() {
+        let mut state = TransitionState::new(Transition::FadeIn {
+            duration: 1.0,
+            color: [0.0, 0.0, 0.0],
+        });
+        assert_eq!(state.progress(), 1.0);
+        state.update(1.0);
+        assert_eq!(state.progress(), 0.0);
+    }
+}
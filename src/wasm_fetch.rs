@@ -0,0 +1,27 @@
+//! The browser-`fetch`-backed byte loader behind
+//! [`crate::Chroma::load_sprite_sheet_async`]. Split out from `lib.rs`
+//! since the `web-sys`/`js-sys` glue is only meaningful on `wasm32`.
+
+use crate::ChromaError;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+pub(crate) async fn fetch_bytes(url: &str) -> Result<Vec<u8>, ChromaError> {
+    let window = web_sys::window().ok_or_else(|| ChromaError::Fetch("no global `window`".into()))?;
+
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|err| ChromaError::Fetch(format!("{err:?}")))?
+        .dyn_into()
+        .map_err(|_| ChromaError::Fetch("fetch did not resolve to a Response".into()))?;
+
+    let array_buffer = JsFuture::from(
+        response
+            .array_buffer()
+            .map_err(|err| ChromaError::Fetch(format!("{err:?}")))?,
+    )
+    .await
+    .map_err(|err| ChromaError::Fetch(format!("{err:?}")))?;
+
+    Ok(js_sys::Uint8Array::new(&array_buffer).to_vec())
+}